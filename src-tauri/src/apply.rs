@@ -0,0 +1,404 @@
+use std::sync::Arc;
+
+use kube::api::{DynamicObject, Patch, PatchParams};
+use kube::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::audit::{object_name, AuditedObject};
+use crate::context::ContextManager;
+use crate::discovery::{ApiResourceInfo, ClusterApiInfo};
+use crate::error::{AppError, Result};
+use crate::resources::dynamic_api;
+use crate::rollout::{diff_lines, DiffLine, DiffLineKind};
+
+/// Field manager recorded in managedFields for everything applied from the app
+pub const FIELD_MANAGER: &str = "apex-kube";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApplyOutcome {
+    Created,
+    Configured,
+    /// Applied without changing the object
+    Unchanged,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApplyResult {
+    pub api_version: String,
+    pub kind: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    /// `None` when the apply failed
+    pub outcome: Option<ApplyOutcome>,
+    pub error: Option<AppError>,
+    /// Failed on fields owned by another field manager; applying again with
+    /// `force_conflicts` takes them over
+    pub field_conflicts: bool,
+}
+
+impl AuditedObject for ApplyResult {
+    fn audit_name(&self) -> String {
+        format!("{} {}", self.kind, object_name(self.namespace.as_deref(), &self.name))
+    }
+
+    fn audit_error(&self) -> Option<&AppError> {
+        self.error.as_ref()
+    }
+}
+
+/// What applying one document would do, from a server-side dry run
+#[derive(Debug, Serialize)]
+pub struct ApplyPreview {
+    pub api_version: String,
+    pub kind: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    /// `None` when the dry run failed
+    pub outcome: Option<ApplyOutcome>,
+    /// JSON paths that differ between the live object and the dry-run result,
+    /// e.g. `spec.replicas` or `spec.template.spec.containers[0].image`
+    pub changed_paths: Vec<String>,
+    /// Line diff of the live object against the dry-run result, as YAML
+    pub lines: Vec<DiffLine>,
+    pub error: Option<AppError>,
+    pub field_conflicts: bool,
+}
+
+/// One YAML document with the fields apply needs, not yet resolved against the
+/// cluster's API
+pub(crate) struct ApplyDocument {
+    pub position: usize,
+    pub api_version: String,
+    pub kind: String,
+    pub name: String,
+    pub object: DynamicObject,
+}
+
+/// A document matched to a served resource, with its namespace filled in
+pub(crate) struct ApplyTarget {
+    pub resource: ApiResourceInfo,
+    pub namespace: Option<String>,
+    pub object: DynamicObject,
+}
+
+/// Parse every document and check it has a type and name before anything is
+/// sent, so a typo in the last document doesn't leave a partial apply
+pub(crate) fn parse_documents(yaml_content: &str) -> Result<Vec<ApplyDocument>> {
+    let mut documents = Vec::new();
+    for (index, doc) in serde_yaml::Deserializer::from_str(yaml_content).enumerate() {
+        let position = index + 1;
+        let mut value = serde_json::Value::deserialize(doc)
+            .map_err(|e| AppError::Invalid(format!("Document {}: {}", position, e)))?;
+        if value.is_null() {
+            continue;
+        }
+        if !value.is_object() {
+            return Err(AppError::Invalid(format!("Document {}: expected a Kubernetes object", position)));
+        }
+        // The API server rejects applies that set managedFields
+        if let Some(metadata) = value.get_mut("metadata").and_then(|m| m.as_object_mut()) {
+            metadata.remove("managedFields");
+        }
+
+        let field = |key: &str| {
+            value
+                .get(key)
+                .and_then(|v| v.as_str())
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+        let (Some(api_version), Some(kind)) = (field("apiVersion"), field("kind")) else {
+            return Err(AppError::Invalid(format!("Document {}: apiVersion and kind are required", position)));
+        };
+
+        let object: DynamicObject = serde_json::from_value(value)
+            .map_err(|e| AppError::Invalid(format!("Document {}: {}", position, e)))?;
+        let Some(name) = object.metadata.name.clone().filter(|n| !n.is_empty()) else {
+            return Err(AppError::Invalid(format!(
+                "Document {}: metadata.name is required (generateName is not supported by apply)",
+                position
+            )));
+        };
+
+        documents.push(ApplyDocument {
+            position,
+            api_version,
+            kind,
+            name,
+            object,
+        });
+    }
+
+    if documents.is_empty() {
+        return Err(AppError::Invalid("No Kubernetes objects found in the YAML".to_string()));
+    }
+    Ok(documents)
+}
+
+/// Looks up resource types in the context's cached discovery. On a miss the
+/// discovery is refreshed once, and again after a CustomResourceDefinition is
+/// applied, so kinds from CRDs created after discovery was cached can be found.
+pub(crate) struct Resolver<'a> {
+    context_manager: &'a ContextManager,
+    context_name: &'a str,
+    info: Arc<ClusterApiInfo>,
+    fresh: bool,
+}
+
+impl<'a> Resolver<'a> {
+    pub async fn new(context_manager: &'a ContextManager, context_name: &'a str) -> Result<Self> {
+        Ok(Self {
+            context_manager,
+            context_name,
+            info: context_manager.api_info(context_name, false).await?,
+            fresh: false,
+        })
+    }
+
+    /// `ClusterApiInfo::find_resource`, refreshing discovery once if the type
+    /// isn't found
+    pub async fn find(&mut self, api_version: Option<&str>, kind: &str) -> Result<ApiResourceInfo> {
+        match self.info.find_resource(api_version, kind) {
+            Ok(resource) => return Ok(resource.clone()),
+            Err(AppError::NotFound(_)) if !self.fresh => {}
+            Err(e) => return Err(e),
+        }
+        self.info = self.context_manager.api_info(self.context_name, true).await?;
+        self.fresh = true;
+        Ok(self.info.find_resource(api_version, kind)?.clone())
+    }
+
+    /// Match a document to a served resource and default its namespace to the
+    /// context's. Fails for that document only.
+    pub async fn resolve(&mut self, client: &Client, doc: &ApplyDocument) -> Result<ApplyTarget> {
+        let position = doc.position;
+        let resource = self
+            .find(Some(&doc.api_version), &doc.kind)
+            .await
+            .map_err(|e| match e {
+                AppError::NotFound(message) => AppError::NotFound(format!("Document {}: {}", position, message)),
+                e => e,
+            })?;
+        if !resource.supports("patch") {
+            return Err(AppError::Invalid(format!(
+                "Document {}: {} ({}) does not support apply",
+                position, resource.kind, resource.api_version
+            )));
+        }
+
+        let mut object = doc.object.clone();
+        let namespace = match (resource.namespaced, object.metadata.namespace.take()) {
+            (true, Some(ns)) => Some(ns),
+            (true, None) => Some(client.default_namespace().to_string()),
+            (false, None) => None,
+            (false, Some(ns)) => {
+                return Err(AppError::Invalid(format!(
+                    "Document {}: {} {} is cluster-scoped but sets namespace '{}'",
+                    position, resource.kind, doc.name, ns
+                )));
+            }
+        };
+        object.metadata.namespace = namespace.clone();
+        Ok(ApplyTarget {
+            resource,
+            namespace,
+            object,
+        })
+    }
+
+    /// Note a successful apply; a new CRD makes the cached discovery stale
+    pub fn applied(&mut self, target: &ApplyTarget) {
+        if target.resource.kind == "CustomResourceDefinition" {
+            self.fresh = false;
+        }
+    }
+}
+
+pub(crate) fn apply_params(force_conflicts: bool, dry_run: bool) -> PatchParams {
+    let mut params = PatchParams::apply(FIELD_MANAGER);
+    if force_conflicts {
+        params = params.force();
+    }
+    if dry_run {
+        params = params.dry_run();
+    }
+    params
+}
+
+/// A 409 from server-side apply lists the fields another manager owns. Stale
+/// resourceVersions are 409s too but can't be forced.
+pub(crate) fn is_field_conflict(e: &AppError) -> bool {
+    matches!(
+        e,
+        AppError::Kube(kube::Error::Api(response))
+            if response.code == 409 && response.message.starts_with("Apply failed with")
+    )
+}
+
+/// Server-side apply every document in a (multi-document) YAML string, in order.
+/// A failed document, including one of a kind the cluster doesn't serve, doesn't
+/// stop the rest; each gets its own result.
+pub async fn apply_yaml(
+    client: &Client,
+    resolver: &mut Resolver<'_>,
+    yaml_content: &str,
+    force_conflicts: bool,
+) -> Result<Vec<ApplyResult>> {
+    let documents = parse_documents(yaml_content)?;
+    let params = apply_params(force_conflicts, false);
+
+    let mut results = Vec::with_capacity(documents.len());
+    for doc in documents {
+        let (namespace, applied) = match resolver.resolve(client, &doc).await {
+            Ok(target) => {
+                let applied = apply_target(client, &target, &params).await;
+                if applied.is_ok() {
+                    resolver.applied(&target);
+                }
+                (target.namespace, applied)
+            }
+            Err(e) => (doc.object.metadata.namespace.clone(), Err(e)),
+        };
+
+        let (outcome, error) = match applied {
+            Ok(outcome) => (Some(outcome), None),
+            Err(e) => (None, Some(e)),
+        };
+        let field_conflicts = error.as_ref().is_some_and(is_field_conflict);
+        results.push(ApplyResult {
+            api_version: doc.api_version,
+            kind: doc.kind,
+            namespace,
+            name: doc.name,
+            outcome,
+            error,
+            field_conflicts,
+        });
+    }
+
+    Ok(results)
+}
+
+async fn apply_target(client: &Client, target: &ApplyTarget, params: &PatchParams) -> Result<ApplyOutcome> {
+    let api = dynamic_api(client, &target.resource, target.namespace.as_deref());
+    let name = target.object.metadata.name.as_deref().unwrap_or_default();
+    let before = api.get_opt(name).await?;
+    let after = api.patch(name, params, &Patch::Apply(&target.object)).await?;
+    Ok(match before {
+        None => ApplyOutcome::Created,
+        Some(before) if before.metadata.resource_version == after.metadata.resource_version => {
+            ApplyOutcome::Unchanged
+        }
+        Some(_) => ApplyOutcome::Configured,
+    })
+}
+
+/// Dry-run apply every document and diff the live object against what the API
+/// server would store. Server-managed fields (managedFields, resourceVersion,
+/// status) are left out so only the edit itself shows up.
+pub async fn preview_apply(
+    client: &Client,
+    resolver: &mut Resolver<'_>,
+    yaml_content: &str,
+    force_conflicts: bool,
+) -> Result<Vec<ApplyPreview>> {
+    let documents = parse_documents(yaml_content)?;
+    let params = apply_params(force_conflicts, true);
+
+    let mut previews = Vec::with_capacity(documents.len());
+    for doc in documents {
+        let (namespace, previewed) = match resolver.resolve(client, &doc).await {
+            Ok(target) => {
+                let previewed = preview_target(client, &target, &params).await;
+                (target.namespace, previewed)
+            }
+            Err(e) => (doc.object.metadata.namespace.clone(), Err(e)),
+        };
+
+        let (outcome, changed_paths, lines, error) = match previewed {
+            Ok((outcome, changed_paths, lines)) => (Some(outcome), changed_paths, lines, None),
+            Err(e) => (None, Vec::new(), Vec::new(), Some(e)),
+        };
+        let field_conflicts = error.as_ref().is_some_and(is_field_conflict);
+        previews.push(ApplyPreview {
+            api_version: doc.api_version,
+            kind: doc.kind,
+            namespace,
+            name: doc.name,
+            outcome,
+            changed_paths,
+            lines,
+            error,
+            field_conflicts,
+        });
+    }
+
+    Ok(previews)
+}
+
+async fn preview_target(
+    client: &Client,
+    target: &ApplyTarget,
+    params: &PatchParams,
+) -> Result<(ApplyOutcome, Vec<String>, Vec<DiffLine>)> {
+    let api = dynamic_api(client, &target.resource, target.namespace.as_deref());
+    let name = target.object.metadata.name.as_deref().unwrap_or_default();
+    let live = api.get_opt(name).await?.map(comparable).transpose()?;
+    let dry_run = comparable(api.patch(name, params, &Patch::Apply(&target.object)).await?)?;
+
+    let mut changed_paths = Vec::new();
+    let before = match &live {
+        Some(live) => {
+            changed_paths_between(live, &dry_run, "", &mut changed_paths);
+            serde_yaml::to_string(live)?
+        }
+        None => String::new(),
+    };
+    let lines = diff_lines(&before, &serde_yaml::to_string(&dry_run)?);
+    let outcome = match live {
+        None => ApplyOutcome::Created,
+        Some(_) if lines.iter().all(|l| l.kind == DiffLineKind::Unchanged) => ApplyOutcome::Unchanged,
+        Some(_) => ApplyOutcome::Configured,
+    };
+    Ok((outcome, changed_paths, lines))
+}
+
+/// The object as JSON without the fields the API server changes on every write
+fn comparable(obj: DynamicObject) -> Result<Value> {
+    let mut value = serde_json::to_value(obj)?;
+    if let Some(object) = value.as_object_mut() {
+        object.remove("status");
+        if let Some(metadata) = object.get_mut("metadata").and_then(Value::as_object_mut) {
+            metadata.remove("managedFields");
+            metadata.remove("resourceVersion");
+        }
+    }
+    Ok(value)
+}
+
+/// Collect the paths where `from` and `to` differ. Arrays of different lengths
+/// are reported as a whole rather than element by element.
+fn changed_paths_between(from: &Value, to: &Value, path: &str, changed: &mut Vec<String>) {
+    match (from, to) {
+        (Value::Object(a), Value::Object(b)) => {
+            let mut keys: Vec<&String> = a.keys().chain(b.keys().filter(|k| !a.contains_key(*k))).collect();
+            keys.sort();
+            for key in keys {
+                let child = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                match (a.get(key), b.get(key)) {
+                    (Some(x), Some(y)) => changed_paths_between(x, y, &child, changed),
+                    _ => changed.push(child),
+                }
+            }
+        }
+        (Value::Array(a), Value::Array(b)) if a.len() == b.len() => {
+            for (index, (x, y)) in a.iter().zip(b).enumerate() {
+                changed_paths_between(x, y, &format!("{}[{}]", path, index), changed);
+            }
+        }
+        (a, b) if a != b => changed.push(path.to_string()),
+        _ => {}
+    }
+}
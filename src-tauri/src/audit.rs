@@ -0,0 +1,250 @@
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::error::{AppError, Result};
+use crate::kubernetes::read_kubeconfig;
+
+const AUDIT_FILE: &str = "audit.jsonl";
+const DEFAULT_QUERY_LIMIT: usize = 500;
+
+/// A mutating action, described before it runs
+#[derive(Debug, Clone)]
+pub struct AuditAction {
+    pub verb: String,
+    pub kind: String,
+    pub namespace: Option<String>,
+    pub name: Option<String>,
+    pub payload: Option<String>,
+}
+
+impl AuditAction {
+    pub fn new(verb: &str, kind: &str, namespace: Option<&str>, name: Option<&str>) -> Self {
+        Self {
+            verb: verb.to_string(),
+            kind: kind.to_string(),
+            namespace: namespace.map(str::to_string),
+            name: name.map(str::to_string),
+            payload: None,
+        }
+    }
+
+    /// Short, human-readable summary of what was sent (never the full body)
+    pub fn with_payload(mut self, payload: impl Into<String>) -> Self {
+        self.payload = Some(payload.into());
+        self
+    }
+}
+
+/// How an action ended, as recorded in the audit log
+#[derive(Debug, Clone, Default)]
+pub struct AuditOutcome {
+    pub success: bool,
+    pub error: Option<String>,
+    /// Objects the action changed, for actions on several objects
+    pub objects: Vec<String>,
+}
+
+/// One object's result from an action on several objects
+pub trait AuditedObject {
+    /// `namespace/name`, or `name` for cluster-scoped objects
+    fn audit_name(&self) -> String;
+    fn audit_error(&self) -> Option<&AppError>;
+}
+
+impl AuditOutcome {
+    pub fn of<T>(result: &Result<T>) -> Self {
+        Self {
+            success: result.is_ok(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+            objects: Vec::new(),
+        }
+    }
+
+    /// Fails if any object failed, and lists the objects that were changed
+    pub fn of_objects<T: AuditedObject>(result: &Result<Vec<T>>) -> Self {
+        let Ok(results) = result else {
+            return Self::of(result);
+        };
+        let failures: Vec<String> = results
+            .iter()
+            .filter_map(|r| r.audit_error().map(|e| format!("{}: {}", r.audit_name(), e)))
+            .collect();
+        Self {
+            success: failures.is_empty(),
+            error: (!failures.is_empty()).then(|| {
+                format!("{} of {} failed: {}", failures.len(), results.len(), failures.join("; "))
+            }),
+            objects: results
+                .iter()
+                .filter(|r| r.audit_error().is_none())
+                .map(|r| r.audit_name())
+                .collect(),
+        }
+    }
+}
+
+/// `namespace/name`, or `name` for cluster-scoped objects
+pub fn object_name(namespace: Option<&str>, name: &str) -> String {
+    match namespace {
+        Some(namespace) => format!("{}/{}", namespace, name),
+        None => name.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: String,
+    pub context: String,
+    /// The kubeconfig user the context authenticates as
+    pub user: Option<String>,
+    pub verb: String,
+    pub kind: String,
+    pub namespace: Option<String>,
+    pub name: Option<String>,
+    pub payload: Option<String>,
+    pub success: bool,
+    pub error: Option<String>,
+    /// Objects changed by an action on several objects, e.g. a delete by label selector
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub objects: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuditQuery {
+    pub context: Option<String>,
+    pub verb: Option<String>,
+    pub kind: Option<String>,
+    pub namespace: Option<String>,
+    /// Substring match on the object name, or any of the objects changed
+    pub name: Option<String>,
+    pub success: Option<bool>,
+    /// RFC 3339 timestamps
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub limit: Option<usize>,
+}
+
+impl AuditQuery {
+    fn matches(&self, entry: &AuditEntry) -> bool {
+        let eq = |filter: &Option<String>, value: &str| {
+            filter.as_deref().is_none_or(|f| f.eq_ignore_ascii_case(value))
+        };
+        eq(&self.context, &entry.context)
+            && eq(&self.verb, &entry.verb)
+            && eq(&self.kind, &entry.kind)
+            && eq(&self.namespace, entry.namespace.as_deref().unwrap_or_default())
+            && self.name.as_deref().is_none_or(|f| {
+                entry.name.as_deref().unwrap_or_default().contains(f)
+                    || entry.objects.iter().any(|object| object.contains(f))
+            })
+            && self.success.is_none_or(|s| s == entry.success)
+            && in_range(&entry.timestamp, self.since.as_deref(), self.until.as_deref())
+    }
+}
+
+fn in_range(timestamp: &str, since: Option<&str>, until: Option<&str>) -> bool {
+    let Ok(ts) = chrono::DateTime::parse_from_rfc3339(timestamp) else {
+        return false;
+    };
+    let after = |bound: Option<&str>| {
+        bound
+            .and_then(|b| chrono::DateTime::parse_from_rfc3339(b).ok())
+            .map(|b| ts >= b)
+            .unwrap_or(true)
+    };
+    let before = |bound: Option<&str>| {
+        bound
+            .and_then(|b| chrono::DateTime::parse_from_rfc3339(b).ok())
+            .map(|b| ts <= b)
+            .unwrap_or(true)
+    };
+    after(since) && before(until)
+}
+
+/// Append-only JSON-lines log of every mutating command, in the app data dir
+pub struct AuditLog {
+    path: PathBuf,
+    // Serializes appends so concurrent commands can't interleave lines
+    write_lock: Mutex<()>,
+}
+
+impl AuditLog {
+    pub fn new(data_dir: PathBuf) -> Self {
+        Self {
+            path: data_dir.join(AUDIT_FILE),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// Record the outcome of an action. Failing to write the log is logged but
+    /// never fails the command itself.
+    pub async fn record(&self, context_name: &str, action: AuditAction, outcome: AuditOutcome) {
+        let entry = AuditEntry {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            context: context_name.to_string(),
+            user: kubeconfig_user(context_name),
+            verb: action.verb,
+            kind: action.kind,
+            namespace: action.namespace,
+            name: action.name,
+            payload: action.payload,
+            success: outcome.success,
+            error: outcome.error,
+            objects: outcome.objects,
+        };
+
+        let _guard = self.write_lock.lock().await;
+        if let Err(e) = self.append(&entry) {
+            tracing::error!("Failed to write audit log {:?}: {}", self.path, e);
+        }
+    }
+
+    fn append(&self, entry: &AuditEntry) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    /// Matching entries, newest first
+    pub async fn query(&self, query: &AuditQuery) -> Result<Vec<AuditEntry>> {
+        let file = match std::fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(AppError::Io(e)),
+        };
+
+        let mut entries: Vec<AuditEntry> = std::io::BufReader::new(file)
+            .lines()
+            .map_while(|line| line.ok())
+            // Skip lines that don't parse (e.g. a partial write after a crash)
+            .filter_map(|line| serde_json::from_str(&line).ok())
+            .filter(|entry| query.matches(entry))
+            .collect();
+
+        entries.reverse();
+        entries.truncate(query.limit.unwrap_or(DEFAULT_QUERY_LIMIT));
+        Ok(entries)
+    }
+}
+
+fn kubeconfig_user(context_name: &str) -> Option<String> {
+    read_kubeconfig()
+        .ok()?
+        .contexts
+        .into_iter()
+        .find(|c| c.name == context_name)?
+        .context?
+        .user
+}
@@ -1,7 +1,15 @@
-use crate::error::Result;
+use crate::apply::{self, ApplyPreview, ApplyResult};
+use crate::audit::{AuditAction, AuditEntry, AuditLog, AuditOutcome, AuditQuery};
+use crate::context::ContextManager;
+use crate::cron::{self, CronJobSchedule};
+use crate::diagnostics::{self, ConnectionDiagnostics};
+use crate::discovery::{ClusterApiInfo, ServerVersion};
+use crate::drain::{self, DrainOptions, DrainStarted, DrainStatus};
+use crate::error::{AppError, Result};
+use crate::kubeconfig::{self, ContextDeleteResult, KubeconfigBackup, KubeconfigImportResult};
 use crate::kubernetes::{
     self, ClusterEventInfo, ClusterMetrics, ConfigMapInfo, ConfigMapDetail, ConfigMapEvent,
-    CronJobInfo, CronJobDetail, CronJobEvent,
+    CronJobInfo, CronJobDetail, CronJobEvent, CronJobJob,
     DaemonSetInfo, DaemonSetDetail, DaemonSetEvent,
     DeploymentDetail, DeploymentEvent, DeploymentInfo,
     HPAInfo, HPADetail, HPAEvent,
@@ -19,10 +27,46 @@ use crate::kubernetes::{
     ServiceInfo, ServiceDetail, ServiceEndpoint, ServiceEvent,
     StatefulSetDetail, StatefulSetEvent, StatefulSetInfo,
 };
+use crate::resources::{self, DeleteOptions, DeleteResult, MetadataChanges, MetadataField, MetadataResult, ScaleInfo};
+use crate::rollout::{self, RevisionDiff, RolloutKind, RolloutRevision, RolloutWatch};
+use crate::settings::{AppSettings, ContextSettings, SettingsManager};
 use crate::portforward::{self, PortForwardManager, PortForwardInfo, ResourceType, AvailablePort};
 use crate::watch::WatchManager;
+use crate::workloads::{self, ContainerResources, JobOverrides, ResourcesPlan, SetImageResult, WorkloadKind};
 use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
 
+/// Run a mutating operation after checking read-only mode and the context's
+/// protection level, and record the outcome in the audit log
+async fn run_mutation<T>(
+    settings: &SettingsManager,
+    audit: &AuditLog,
+    context_name: &str,
+    confirm: Option<&str>,
+    action: AuditAction,
+    operation: impl std::future::Future<Output = Result<T>>,
+) -> Result<T> {
+    run_audited(settings, audit, context_name, confirm, action, AuditOutcome::of, operation).await
+}
+
+/// `run_mutation` for operations whose audit outcome depends on what they
+/// return, such as bulk actions that report per-object failures inside `Ok`
+async fn run_audited<T>(
+    settings: &SettingsManager,
+    audit: &AuditLog,
+    context_name: &str,
+    confirm: Option<&str>,
+    action: AuditAction,
+    outcome: impl FnOnce(&Result<T>) -> AuditOutcome,
+    operation: impl std::future::Future<Output = Result<T>>,
+) -> Result<T> {
+    let result = match settings.ensure_mutation_allowed(context_name, confirm).await {
+        Ok(()) => operation.await,
+        Err(e) => Err(e),
+    };
+    audit.record(context_name, action, outcome(&result)).await;
+    result
+}
+
 // Startup check commands
 #[tauri::command]
 pub async fn check_kubeconfig() -> Result<String> {
@@ -39,47 +83,167 @@ pub async fn get_context_names() -> Result<Vec<String>> {
     kubernetes::get_context_names().await
 }
 
+/// Diagnose the connection to the active context. Connection problems are
+/// reported in the returned diagnostics rather than as an error.
+#[tauri::command]
+pub async fn test_cluster_connection(
+    context_manager: tauri::State<'_, ContextManager>,
+) -> Result<ConnectionDiagnostics> {
+    let context_name = context_manager.active_context().await?;
+    Ok(diagnostics::diagnose_connection(&context_name).await)
+}
+
+#[tauri::command]
+pub async fn get_contexts(
+    context_manager: tauri::State<'_, ContextManager>,
+) -> Result<Vec<KubeContext>> {
+    let active = context_manager.active_context().await.ok();
+    let contexts = kubernetes::list_contexts().await?;
+    Ok(contexts
+        .into_iter()
+        .map(|ctx| KubeContext {
+            is_current: Some(&ctx.name) == active.as_ref(),
+            ..ctx
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn get_current_context(
+    context_manager: tauri::State<'_, ContextManager>,
+) -> Result<String> {
+    context_manager.active_context().await
+}
+
+/// Switch the app's active context. The kubeconfig file is only rewritten when
+/// `persist` is true; otherwise the change stays local to the app.
+#[tauri::command]
+pub async fn switch_context(
+    context_manager: tauri::State<'_, ContextManager>,
+    context_name: String,
+    persist: Option<bool>,
+) -> Result<()> {
+    context_manager
+        .set_active_context(&context_name, persist.unwrap_or(false))
+        .await
+}
+
+// ============ Context Settings Commands ============
+
+#[tauri::command]
+pub async fn get_app_settings(settings: tauri::State<'_, SettingsManager>) -> Result<AppSettings> {
+    Ok(settings.settings().await)
+}
+
+/// Turn app-wide read-only mode on or off. Turning it off needs `confirm` to be
+/// `settings::READ_WRITE_CONFIRMATION`.
+#[tauri::command]
+pub async fn set_read_only_mode(
+    settings: tauri::State<'_, SettingsManager>,
+    enabled: bool,
+    confirm: Option<String>,
+) -> Result<()> {
+    settings.set_read_only(enabled, confirm.as_deref()).await
+}
+
+/// Set a context's colour, label and protection level. Lowering the protection
+/// level needs `confirm` to be the context name.
+#[tauri::command]
+pub async fn set_context_settings(
+    settings: tauri::State<'_, SettingsManager>,
+    context_name: String,
+    context_settings: ContextSettings,
+    confirm: Option<String>,
+) -> Result<()> {
+    settings
+        .set_context_settings(&context_name, context_settings, confirm.as_deref())
+        .await
+}
+
+// ============ Audit Log Commands ============
+
+/// Query the audit log, newest first
+#[tauri::command]
+pub async fn get_audit_log(
+    audit: tauri::State<'_, AuditLog>,
+    query: Option<AuditQuery>,
+) -> Result<Vec<AuditEntry>> {
+    audit.query(&query.unwrap_or_default()).await
+}
+
+// ============ Kubeconfig Editing Commands ============
+
+#[tauri::command]
+pub async fn import_kubeconfig(source_path: String) -> Result<KubeconfigImportResult> {
+    kubeconfig::import_kubeconfig(&source_path)
+}
+
+#[tauri::command]
+pub async fn rename_context(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    old_name: String,
+    new_name: String,
+) -> Result<()> {
+    kubeconfig::rename_context(&old_name, &new_name)?;
+    context_manager.context_renamed(&old_name, &new_name).await;
+    settings.rename_context(&old_name, &new_name).await
+}
+
 #[tauri::command]
-pub async fn test_cluster_connection() -> Result<()> {
-    kubernetes::test_connection().await
+pub async fn delete_context(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    context_name: String,
+) -> Result<ContextDeleteResult> {
+    let result = kubeconfig::delete_context(&context_name)?;
+    context_manager.context_deleted(&context_name).await;
+    settings.remove_context(&context_name).await?;
+    Ok(result)
 }
 
 #[tauri::command]
-pub async fn get_contexts() -> Result<Vec<KubeContext>> {
-    kubernetes::list_contexts().await
+pub async fn set_context_namespace(context_name: String, namespace: Option<String>) -> Result<()> {
+    kubeconfig::set_context_namespace(&context_name, namespace.as_deref())
 }
 
 #[tauri::command]
-pub async fn get_current_context() -> Result<String> {
-    kubernetes::get_current_context_name().await
+pub async fn list_kubeconfig_backups() -> Result<Vec<KubeconfigBackup>> {
+    kubeconfig::list_kubeconfig_backups()
 }
 
 #[tauri::command]
-pub async fn switch_context(context_name: String) -> Result<()> {
-    kubernetes::switch_to_context(&context_name).await
+pub async fn restore_kubeconfig_backup(backup_path: String) -> Result<KubeconfigBackup> {
+    kubeconfig::restore_kubeconfig_backup(&backup_path)
 }
 
 #[tauri::command]
-pub async fn get_namespaces() -> Result<Vec<String>> {
-    let client = kubernetes::create_client().await?;
+pub async fn get_namespaces(
+    context_manager: tauri::State<'_, ContextManager>,
+) -> Result<Vec<String>> {
+    let client = context_manager.create_client().await?;
     kubernetes::list_namespaces(&client).await
 }
 
 #[tauri::command]
-pub async fn get_pods(namespace: Option<String>) -> Result<Vec<PodInfo>> {
-    let client = kubernetes::create_client().await?;
+pub async fn get_pods(
+    context_manager: tauri::State<'_, ContextManager>,
+    namespace: Option<String>,
+) -> Result<Vec<PodInfo>> {
+    let client = context_manager.create_client().await?;
     kubernetes::list_pods(&client, namespace.as_deref()).await
 }
 
 #[tauri::command]
 pub async fn get_pod_logs(
+    context_manager: tauri::State<'_, ContextManager>,
     namespace: String,
     pod_name: String,
     container: Option<String>,
     tail_lines: Option<i64>,
     previous: Option<bool>,
 ) -> Result<String> {
-    let client = kubernetes::create_client().await?;
+    let client = context_manager.create_client().await?;
     kubernetes::get_logs(
         &client,
         &namespace,
@@ -92,158 +256,365 @@ pub async fn get_pod_logs(
 }
 
 #[tauri::command]
-pub async fn delete_pod(namespace: String, pod_name: String) -> Result<()> {
-    let client = kubernetes::create_client().await?;
-    kubernetes::delete_pod_by_name(&client, &namespace, &pod_name).await
+pub async fn delete_pod(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    namespace: String,
+    pod_name: String,
+    confirm: Option<String>,
+) -> Result<()> {
+    let action = AuditAction::new("delete", "Pod", Some(&namespace), Some(&pod_name));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        kubernetes::delete_pod_by_name(&client, &namespace, &pod_name).await
+    })
+    .await
+}
+
+/// Evict a pod, honouring PodDisruptionBudgets. Requires `acknowledge_risk`: the
+/// pod is gone once evicted, and only comes back if a controller recreates it.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn evict_pod(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    namespace: String,
+    pod_name: String,
+    grace_period_seconds: Option<u32>,
+    acknowledge_risk: bool,
+    confirm: Option<String>,
+) -> Result<()> {
+    if !acknowledge_risk {
+        return Err(AppError::Invalid(
+            "Evicted pods are only recreated if a controller owns them: acknowledge the risk to continue".to_string(),
+        ));
+    }
+    let action = AuditAction::new("evict", "Pod", Some(&namespace), Some(&pod_name));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        kubernetes::evict_pod_by_name(&client, &namespace, &pod_name, grace_period_seconds).await
+    })
+    .await
+}
+
+/// Force delete a pod stuck in Terminating. Requires `acknowledge_risk`: the
+/// containers may still be running on an unreachable node.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn force_delete_pod(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    namespace: String,
+    pod_name: String,
+    acknowledge_risk: bool,
+    confirm: Option<String>,
+) -> Result<()> {
+    if !acknowledge_risk {
+        return Err(AppError::Invalid(
+            "Force delete skips graceful termination: acknowledge the risk to continue".to_string(),
+        ));
+    }
+    let action = AuditAction::new("force-delete", "Pod", Some(&namespace), Some(&pod_name));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        kubernetes::force_delete_pod_by_name(&client, &namespace, &pod_name).await
+    })
+    .await
 }
 
 #[tauri::command]
-pub async fn get_deployments(namespace: Option<String>) -> Result<Vec<DeploymentInfo>> {
-    let client = kubernetes::create_client().await?;
+pub async fn get_deployments(
+    context_manager: tauri::State<'_, ContextManager>,
+    namespace: Option<String>,
+) -> Result<Vec<DeploymentInfo>> {
+    let client = context_manager.create_client().await?;
     kubernetes::list_deployments(&client, namespace.as_deref()).await
 }
 
 // ============ Deployment Commands ============
 
 #[tauri::command]
-pub async fn scale_deployment(namespace: String, name: String, replicas: i32) -> Result<()> {
-    let client = kubernetes::create_client().await?;
-    kubernetes::scale_deployment(&client, &namespace, &name, replicas).await
+#[allow(clippy::too_many_arguments)]
+pub async fn scale_deployment(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    namespace: String,
+    name: String,
+    replicas: i32,
+    confirm: Option<String>,
+) -> Result<()> {
+    let action = AuditAction::new("scale", "Deployment", Some(&namespace), Some(&name))
+        .with_payload(format!("replicas={}", replicas));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        kubernetes::scale_deployment(&client, &namespace, &name, replicas).await
+    })
+    .await
 }
 
 #[tauri::command]
-pub async fn restart_deployment(namespace: String, name: String) -> Result<()> {
-    let client = kubernetes::create_client().await?;
-    kubernetes::restart_deployment(&client, &namespace, &name).await
+pub async fn restart_deployment(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    namespace: String,
+    name: String,
+    confirm: Option<String>,
+) -> Result<()> {
+    let action = AuditAction::new("restart", "Deployment", Some(&namespace), Some(&name));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        kubernetes::restart_deployment(&client, &namespace, &name).await
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn pause_deployment(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    namespace: String,
+    name: String,
+    confirm: Option<String>,
+) -> Result<()> {
+    let action = AuditAction::new("pause", "Deployment", Some(&namespace), Some(&name));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        kubernetes::set_deployment_paused(&client, &namespace, &name, true).await
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn resume_deployment(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    namespace: String,
+    name: String,
+    confirm: Option<String>,
+) -> Result<()> {
+    let action = AuditAction::new("resume", "Deployment", Some(&namespace), Some(&name));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        kubernetes::set_deployment_paused(&client, &namespace, &name, false).await
+    })
+    .await
 }
 
 #[tauri::command]
 pub async fn get_deployment_detail(
+    context_manager: tauri::State<'_, ContextManager>,
     context_name: String,
     namespace: String,
     name: String,
 ) -> Result<DeploymentDetail> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_deployment_detail(&client, &namespace, &name).await
 }
 
 #[tauri::command]
 pub async fn get_deployment_yaml(
+    context_manager: tauri::State<'_, ContextManager>,
     context_name: String,
     namespace: String,
     name: String,
 ) -> Result<String> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_deployment_yaml(&client, &namespace, &name).await
 }
 
 #[tauri::command]
 pub async fn get_deployment_events(
+    context_manager: tauri::State<'_, ContextManager>,
     context_name: String,
     namespace: String,
     name: String,
 ) -> Result<Vec<DeploymentEvent>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_deployment_events(&client, &namespace, &name).await
 }
 
 #[tauri::command]
 pub async fn get_deployment_pods(
+    context_manager: tauri::State<'_, ContextManager>,
     context_name: String,
     namespace: String,
     name: String,
 ) -> Result<Vec<PodInfo>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_deployment_pods(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_statefulsets(namespace: Option<String>) -> Result<Vec<StatefulSetInfo>> {
-    let client = kubernetes::create_client().await?;
+pub async fn get_statefulsets(
+    context_manager: tauri::State<'_, ContextManager>,
+    namespace: Option<String>,
+) -> Result<Vec<StatefulSetInfo>> {
+    let client = context_manager.create_client().await?;
     kubernetes::list_statefulsets(&client, namespace.as_deref()).await
 }
 
 // ============ StatefulSet Commands ============
 
 #[tauri::command]
-pub async fn scale_statefulset(namespace: String, name: String, replicas: i32) -> Result<()> {
-    let client = kubernetes::create_client().await?;
-    kubernetes::scale_statefulset(&client, &namespace, &name, replicas).await
+#[allow(clippy::too_many_arguments)]
+pub async fn scale_statefulset(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    namespace: String,
+    name: String,
+    replicas: i32,
+    confirm: Option<String>,
+) -> Result<()> {
+    let action = AuditAction::new("scale", "StatefulSet", Some(&namespace), Some(&name))
+        .with_payload(format!("replicas={}", replicas));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        kubernetes::scale_statefulset(&client, &namespace, &name, replicas).await
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn restart_statefulset(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    namespace: String,
+    name: String,
+    confirm: Option<String>,
+) -> Result<()> {
+    let action = AuditAction::new("restart", "StatefulSet", Some(&namespace), Some(&name));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        kubernetes::restart_statefulset(&client, &namespace, &name).await
+    })
+    .await
 }
 
 #[tauri::command]
-pub async fn restart_statefulset(namespace: String, name: String) -> Result<()> {
-    let client = kubernetes::create_client().await?;
-    kubernetes::restart_statefulset(&client, &namespace, &name).await
+#[allow(clippy::too_many_arguments)]
+pub async fn set_statefulset_partition(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    namespace: String,
+    name: String,
+    partition: i32,
+    confirm: Option<String>,
+) -> Result<()> {
+    let action = AuditAction::new("partition", "StatefulSet", Some(&namespace), Some(&name))
+        .with_payload(format!("partition={}", partition));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        kubernetes::set_statefulset_partition(&client, &namespace, &name, partition).await
+    })
+    .await
 }
 
 #[tauri::command]
 pub async fn get_statefulset_detail(
+    context_manager: tauri::State<'_, ContextManager>,
     context_name: String,
     namespace: String,
     name: String,
 ) -> Result<StatefulSetDetail> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_statefulset_detail(&client, &namespace, &name).await
 }
 
 #[tauri::command]
 pub async fn get_statefulset_yaml(
+    context_manager: tauri::State<'_, ContextManager>,
     context_name: String,
     namespace: String,
     name: String,
 ) -> Result<String> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_statefulset_yaml(&client, &namespace, &name).await
 }
 
 #[tauri::command]
 pub async fn get_statefulset_events(
+    context_manager: tauri::State<'_, ContextManager>,
     context_name: String,
     namespace: String,
     name: String,
 ) -> Result<Vec<StatefulSetEvent>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_statefulset_events(&client, &namespace, &name).await
 }
 
 #[tauri::command]
 pub async fn get_statefulset_pods(
+    context_manager: tauri::State<'_, ContextManager>,
     context_name: String,
     namespace: String,
     name: String,
 ) -> Result<Vec<PodInfo>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_statefulset_pods(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_daemonsets(namespace: Option<String>) -> Result<Vec<DaemonSetInfo>> {
-    let client = kubernetes::create_client().await?;
+pub async fn get_daemonsets(
+    context_manager: tauri::State<'_, ContextManager>,
+    namespace: Option<String>,
+) -> Result<Vec<DaemonSetInfo>> {
+    let client = context_manager.create_client().await?;
     kubernetes::list_daemonsets(&client, namespace.as_deref()).await
 }
 
 #[tauri::command]
-pub async fn get_replicasets(namespace: Option<String>) -> Result<Vec<ReplicaSetInfo>> {
-    let client = kubernetes::create_client().await?;
+pub async fn get_replicasets(
+    context_manager: tauri::State<'_, ContextManager>,
+    namespace: Option<String>,
+) -> Result<Vec<ReplicaSetInfo>> {
+    let client = context_manager.create_client().await?;
     kubernetes::list_replicasets(&client, namespace.as_deref()).await
 }
 
 #[tauri::command]
-pub async fn get_jobs(namespace: Option<String>) -> Result<Vec<JobInfo>> {
-    let client = kubernetes::create_client().await?;
+pub async fn get_jobs(
+    context_manager: tauri::State<'_, ContextManager>,
+    namespace: Option<String>,
+) -> Result<Vec<JobInfo>> {
+    let client = context_manager.create_client().await?;
     kubernetes::list_jobs(&client, namespace.as_deref()).await
 }
 
 #[tauri::command]
-pub async fn get_cronjobs(namespace: Option<String>) -> Result<Vec<CronJobInfo>> {
-    let client = kubernetes::create_client().await?;
+pub async fn get_cronjobs(
+    context_manager: tauri::State<'_, ContextManager>,
+    namespace: Option<String>,
+) -> Result<Vec<CronJobInfo>> {
+    let client = context_manager.create_client().await?;
     kubernetes::list_cronjobs(&client, namespace.as_deref()).await
 }
 
 #[tauri::command]
-pub async fn get_services(namespace: Option<String>) -> Result<Vec<ServiceInfo>> {
-    let client = kubernetes::create_client().await?;
+pub async fn get_services(
+    context_manager: tauri::State<'_, ContextManager>,
+    namespace: Option<String>,
+) -> Result<Vec<ServiceInfo>> {
+    let client = context_manager.create_client().await?;
     kubernetes::list_services(&client, namespace.as_deref()).await
 }
 
@@ -251,48 +622,55 @@ pub async fn get_services(namespace: Option<String>) -> Result<Vec<ServiceInfo>>
 
 #[tauri::command]
 pub async fn get_service_detail(
+    context_manager: tauri::State<'_, ContextManager>,
     context_name: String,
     namespace: String,
     name: String,
 ) -> Result<ServiceDetail> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_service_detail(&client, &namespace, &name).await
 }
 
 #[tauri::command]
 pub async fn get_service_yaml(
+    context_manager: tauri::State<'_, ContextManager>,
     context_name: String,
     namespace: String,
     name: String,
 ) -> Result<String> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_service_yaml(&client, &namespace, &name).await
 }
 
 #[tauri::command]
 pub async fn get_service_events(
+    context_manager: tauri::State<'_, ContextManager>,
     context_name: String,
     namespace: String,
     name: String,
 ) -> Result<Vec<ServiceEvent>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_service_events(&client, &namespace, &name).await
 }
 
 #[tauri::command]
 pub async fn get_service_endpoints(
+    context_manager: tauri::State<'_, ContextManager>,
     context_name: String,
     namespace: String,
     name: String,
 ) -> Result<Vec<ServiceEndpoint>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_service_endpoints(&client, &namespace, &name).await
 }
 
 // Network resources
 #[tauri::command]
-pub async fn get_ingresses(namespace: Option<String>) -> Result<Vec<IngressInfo>> {
-    let client = kubernetes::create_client().await?;
+pub async fn get_ingresses(
+    context_manager: tauri::State<'_, ContextManager>,
+    namespace: Option<String>,
+) -> Result<Vec<IngressInfo>> {
+    let client = context_manager.create_client().await?;
     kubernetes::list_ingresses(&client, namespace.as_deref()).await
 }
 
@@ -300,107 +678,172 @@ pub async fn get_ingresses(namespace: Option<String>) -> Result<Vec<IngressInfo>
 
 #[tauri::command]
 pub async fn get_ingress_detail(
+    context_manager: tauri::State<'_, ContextManager>,
     context_name: String,
     namespace: String,
     name: String,
 ) -> Result<IngressDetail> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_ingress_detail(&client, &namespace, &name).await
 }
 
 #[tauri::command]
 pub async fn get_ingress_yaml(
+    context_manager: tauri::State<'_, ContextManager>,
     context_name: String,
     namespace: String,
     name: String,
 ) -> Result<String> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_ingress_yaml(&client, &namespace, &name).await
 }
 
 #[tauri::command]
 pub async fn get_ingress_events(
+    context_manager: tauri::State<'_, ContextManager>,
     context_name: String,
     namespace: String,
     name: String,
 ) -> Result<Vec<IngressEvent>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_ingress_events(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_network_policies(namespace: Option<String>) -> Result<Vec<NetworkPolicyInfo>> {
-    let client = kubernetes::create_client().await?;
+pub async fn get_network_policies(
+    context_manager: tauri::State<'_, ContextManager>,
+    namespace: Option<String>,
+) -> Result<Vec<NetworkPolicyInfo>> {
+    let client = context_manager.create_client().await?;
     kubernetes::list_network_policies(&client, namespace.as_deref()).await
 }
 
 // Config resources
 #[tauri::command]
-pub async fn get_configmaps(namespace: Option<String>) -> Result<Vec<ConfigMapInfo>> {
-    let client = kubernetes::create_client().await?;
+pub async fn get_configmaps(
+    context_manager: tauri::State<'_, ContextManager>,
+    namespace: Option<String>,
+) -> Result<Vec<ConfigMapInfo>> {
+    let client = context_manager.create_client().await?;
     kubernetes::list_configmaps(&client, namespace.as_deref()).await
 }
 
 #[tauri::command]
-pub async fn get_secrets(namespace: Option<String>) -> Result<Vec<SecretInfo>> {
-    let client = kubernetes::create_client().await?;
+pub async fn get_secrets(
+    context_manager: tauri::State<'_, ContextManager>,
+    namespace: Option<String>,
+) -> Result<Vec<SecretInfo>> {
+    let client = context_manager.create_client().await?;
     kubernetes::list_secrets(&client, namespace.as_deref()).await
 }
 
 #[tauri::command]
-pub async fn get_hpas(namespace: Option<String>) -> Result<Vec<HPAInfo>> {
-    let client = kubernetes::create_client().await?;
+pub async fn get_hpas(
+    context_manager: tauri::State<'_, ContextManager>,
+    namespace: Option<String>,
+) -> Result<Vec<HPAInfo>> {
+    let client = context_manager.create_client().await?;
     kubernetes::list_hpas(&client, namespace.as_deref()).await
 }
 
 // Storage resources
 #[tauri::command]
-pub async fn get_pvs() -> Result<Vec<PersistentVolumeInfo>> {
-    let client = kubernetes::create_client().await?;
+pub async fn get_pvs(context_manager: tauri::State<'_, ContextManager>) -> Result<Vec<PersistentVolumeInfo>> {
+    let client = context_manager.create_client().await?;
     kubernetes::list_pvs(&client).await
 }
 
 #[tauri::command]
-pub async fn get_pvcs(namespace: Option<String>) -> Result<Vec<PersistentVolumeClaimInfo>> {
-    let client = kubernetes::create_client().await?;
+pub async fn get_pvcs(
+    context_manager: tauri::State<'_, ContextManager>,
+    namespace: Option<String>,
+) -> Result<Vec<PersistentVolumeClaimInfo>> {
+    let client = context_manager.create_client().await?;
     kubernetes::list_pvcs(&client, namespace.as_deref()).await
 }
 
 // Cluster resources
 #[tauri::command]
-pub async fn get_namespaces_info() -> Result<Vec<NamespaceInfo>> {
-    let client = kubernetes::create_client().await?;
+pub async fn get_namespaces_info(context_manager: tauri::State<'_, ContextManager>) -> Result<Vec<NamespaceInfo>> {
+    let client = context_manager.create_client().await?;
     kubernetes::list_namespaces_info(&client).await
 }
 
 #[tauri::command]
-pub async fn get_nodes() -> Result<Vec<NodeInfo>> {
-    let client = kubernetes::create_client().await?;
+pub async fn get_nodes(context_manager: tauri::State<'_, ContextManager>) -> Result<Vec<NodeInfo>> {
+    let client = context_manager.create_client().await?;
     kubernetes::list_nodes(&client).await
 }
 
 #[tauri::command]
-pub async fn get_service_accounts(namespace: Option<String>) -> Result<Vec<ServiceAccountInfo>> {
-    let client = kubernetes::create_client().await?;
+pub async fn get_service_accounts(
+    context_manager: tauri::State<'_, ContextManager>,
+    namespace: Option<String>,
+) -> Result<Vec<ServiceAccountInfo>> {
+    let client = context_manager.create_client().await?;
     kubernetes::list_service_accounts(&client, namespace.as_deref()).await
 }
 
 #[tauri::command]
-pub async fn get_events(namespace: Option<String>) -> Result<Vec<ClusterEventInfo>> {
-    let client = kubernetes::create_client().await?;
+pub async fn get_events(
+    context_manager: tauri::State<'_, ContextManager>,
+    namespace: Option<String>,
+) -> Result<Vec<ClusterEventInfo>> {
+    let client = context_manager.create_client().await?;
     kubernetes::list_events(&client, namespace.as_deref()).await
 }
 
 #[tauri::command]
-pub async fn get_cluster_metrics() -> Result<ClusterMetrics> {
-    let client = kubernetes::create_client().await?;
+pub async fn get_cluster_metrics(context_manager: tauri::State<'_, ContextManager>) -> Result<ClusterMetrics> {
+    let client = context_manager.create_client().await?;
     kubernetes::get_metrics(&client).await
 }
 
 #[tauri::command]
-pub async fn get_pulse_metrics(namespace: Option<String>) -> Result<PulseMetrics> {
-    let client = kubernetes::create_client().await?;
-    kubernetes::get_pulse_metrics(&client, namespace.as_deref()).await
+pub async fn get_pulse_metrics(
+    context_manager: tauri::State<'_, ContextManager>,
+    namespace: Option<String>,
+) -> Result<PulseMetrics> {
+    let context_name = context_manager.active_context().await?;
+    let client = context_manager.client_for_context(&context_name).await?;
+    let k8s_version = match context_manager.api_info(&context_name, false).await {
+        Ok(info) => info.server_version.git_version.clone(),
+        Err(_) => "Unknown".to_string(),
+    };
+    kubernetes::get_pulse_metrics(&client, &context_name, &k8s_version, namespace.as_deref()).await
+}
+
+// ============ API Discovery Commands ============
+
+#[tauri::command]
+pub async fn get_server_version(
+    context_manager: tauri::State<'_, ContextManager>,
+    context_name: Option<String>,
+) -> Result<ServerVersion> {
+    let context_name = match context_name {
+        Some(name) => name,
+        None => context_manager.active_context().await?,
+    };
+    let info = context_manager.api_info(&context_name, false).await?;
+    Ok(info.server_version.clone())
+}
+
+/// Served API groups and resources (including CRDs). Cached per context; pass
+/// `refresh` to rediscover, e.g. after installing CRDs.
+#[tauri::command]
+pub async fn get_api_resources(
+    context_manager: tauri::State<'_, ContextManager>,
+    context_name: Option<String>,
+    refresh: Option<bool>,
+) -> Result<ClusterApiInfo> {
+    let context_name = match context_name {
+        Some(name) => name,
+        None => context_manager.active_context().await?,
+    };
+    let info = context_manager
+        .api_info(&context_name, refresh.unwrap_or(false))
+        .await?;
+    Ok((*info).clone())
 }
 
 #[tauri::command]
@@ -418,31 +861,34 @@ pub async fn exec_pod(
 
 #[tauri::command]
 pub async fn get_pod_detail(
+    context_manager: tauri::State<'_, ContextManager>,
     context_name: String,
     namespace: String,
     name: String,
 ) -> Result<PodDetail> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_pod_detail(&client, &namespace, &name).await
 }
 
 #[tauri::command]
 pub async fn get_pod_yaml(
+    context_manager: tauri::State<'_, ContextManager>,
     context_name: String,
     namespace: String,
     name: String,
 ) -> Result<String> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_pod_yaml(&client, &namespace, &name).await
 }
 
 #[tauri::command]
 pub async fn get_pod_events(
+    context_manager: tauri::State<'_, ContextManager>,
     context_name: String,
     namespace: String,
     name: String,
 ) -> Result<Vec<PodEvent>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_pod_events(&client, &namespace, &name).await
 }
 
@@ -583,325 +1029,549 @@ pub async fn open_terminal_window(
 // ============ ConfigMap Detail Commands ============
 
 #[tauri::command]
-pub async fn get_configmap_detail(context_name: String, namespace: String, name: String) -> Result<ConfigMapDetail> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_configmap_detail(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<ConfigMapDetail> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_configmap_detail(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_configmap_yaml(context_name: String, namespace: String, name: String) -> Result<String> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_configmap_yaml(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<String> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_configmap_yaml(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_configmap_events(context_name: String, namespace: String, name: String) -> Result<Vec<ConfigMapEvent>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_configmap_events(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<Vec<ConfigMapEvent>> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_configmap_events(&client, &namespace, &name).await
 }
 
 // ============ Secret Detail Commands ============
 
 #[tauri::command]
-pub async fn get_secret_detail(context_name: String, namespace: String, name: String) -> Result<SecretDetail> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_secret_detail(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<SecretDetail> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_secret_detail(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_secret_yaml(context_name: String, namespace: String, name: String) -> Result<String> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_secret_yaml(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<String> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_secret_yaml(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_secret_data(context_name: String, namespace: String, name: String) -> Result<std::collections::BTreeMap<String, String>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_secret_data(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<std::collections::BTreeMap<String, String>> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_secret_data(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_secret_events(context_name: String, namespace: String, name: String) -> Result<Vec<SecretEvent>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_secret_events(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<Vec<SecretEvent>> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_secret_events(&client, &namespace, &name).await
 }
 
 // ============ Job Detail Commands ============
 
 #[tauri::command]
-pub async fn get_job_detail(context_name: String, namespace: String, name: String) -> Result<JobDetail> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_job_detail(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<JobDetail> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_job_detail(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_job_yaml(context_name: String, namespace: String, name: String) -> Result<String> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_job_yaml(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<String> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_job_yaml(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_job_events(context_name: String, namespace: String, name: String) -> Result<Vec<JobEvent>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_job_events(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<Vec<JobEvent>> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_job_events(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_job_pods(context_name: String, namespace: String, job_name: String) -> Result<Vec<PodInfo>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_job_pods(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, job_name: String) -> Result<Vec<PodInfo>> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_job_pods(&client, &namespace, &job_name).await
 }
 
+/// Create a new Job from a finished or failed one, optionally changing a
+/// container's image, command or env. Returns the new Job's name.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn rerun_job(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    namespace: String,
+    name: String,
+    new_name: Option<String>,
+    overrides: Option<JobOverrides>,
+    confirm: Option<String>,
+) -> Result<String> {
+    let overrides = overrides.unwrap_or_default();
+    let action = AuditAction::new("rerun", "Job", Some(&namespace), Some(&name)).with_payload(overrides.summary());
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        workloads::rerun_job(&client, &namespace, &name, new_name.as_deref(), &overrides).await
+    })
+    .await
+}
+
 // ============ CronJob Detail Commands ============
 
 #[tauri::command]
-pub async fn get_cronjob_detail(context_name: String, namespace: String, name: String) -> Result<CronJobDetail> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_cronjob_detail(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<CronJobDetail> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_cronjob_detail(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_cronjob_yaml(context_name: String, namespace: String, name: String) -> Result<String> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_cronjob_yaml(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<String> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_cronjob_yaml(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_cronjob_events(context_name: String, namespace: String, name: String) -> Result<Vec<CronJobEvent>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_cronjob_events(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<Vec<CronJobEvent>> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_cronjob_events(&client, &namespace, &name).await
 }
 
+#[tauri::command]
+pub async fn get_cronjob_jobs(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<Vec<CronJobJob>> {
+    let client = context_manager.client_for_context(&context_name).await?;
+    kubernetes::get_cronjob_jobs(&client, &namespace, &name).await
+}
+
+/// Next `count` run times (default 5) plus missed runs and scheduling warnings
+#[tauri::command]
+pub async fn get_cronjob_schedule(
+    context_manager: tauri::State<'_, ContextManager>,
+    context_name: String,
+    namespace: String,
+    name: String,
+    count: Option<usize>,
+) -> Result<CronJobSchedule> {
+    let client = context_manager.client_for_context(&context_name).await?;
+    cron::get_cronjob_schedule(&client, &namespace, &name, count.unwrap_or(5).min(50)).await
+}
+
+/// Create a Job from the CronJob's template now; returns the Job's name
+#[tauri::command]
+pub async fn trigger_cronjob(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    namespace: String,
+    name: String,
+    confirm: Option<String>,
+) -> Result<String> {
+    let action = AuditAction::new("trigger", "CronJob", Some(&namespace), Some(&name));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        kubernetes::trigger_cronjob(&client, &namespace, &name).await
+    })
+    .await
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn set_cronjob_suspended(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    namespace: String,
+    name: String,
+    suspend: bool,
+    confirm: Option<String>,
+) -> Result<()> {
+    let verb = if suspend { "suspend" } else { "resume" };
+    let action = AuditAction::new(verb, "CronJob", Some(&namespace), Some(&name));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        kubernetes::set_cronjob_suspended(&client, &namespace, &name, suspend).await
+    })
+    .await
+}
+
 // ============ DaemonSet Detail Commands ============
 
 #[tauri::command]
-pub async fn get_daemonset_detail(context_name: String, namespace: String, name: String) -> Result<DaemonSetDetail> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn restart_daemonset(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    namespace: String,
+    name: String,
+    confirm: Option<String>,
+) -> Result<()> {
+    let action = AuditAction::new("restart", "DaemonSet", Some(&namespace), Some(&name));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        kubernetes::restart_daemonset(&client, &namespace, &name).await
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn get_daemonset_detail(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<DaemonSetDetail> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_daemonset_detail(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_daemonset_yaml(context_name: String, namespace: String, name: String) -> Result<String> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_daemonset_yaml(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<String> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_daemonset_yaml(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_daemonset_events(context_name: String, namespace: String, name: String) -> Result<Vec<DaemonSetEvent>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_daemonset_events(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<Vec<DaemonSetEvent>> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_daemonset_events(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_daemonset_pods(context_name: String, namespace: String, daemonset_name: String) -> Result<Vec<PodInfo>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_daemonset_pods(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, daemonset_name: String) -> Result<Vec<PodInfo>> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_daemonset_pods(&client, &namespace, &daemonset_name).await
 }
 
 // ============ ReplicaSet Detail Commands ============
 
 #[tauri::command]
-pub async fn get_replicaset_detail(context_name: String, namespace: String, name: String) -> Result<ReplicaSetDetail> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+#[allow(clippy::too_many_arguments)]
+pub async fn scale_replicaset(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    namespace: String,
+    name: String,
+    replicas: i32,
+    confirm: Option<String>,
+) -> Result<()> {
+    let action = AuditAction::new("scale", "ReplicaSet", Some(&namespace), Some(&name))
+        .with_payload(format!("replicas={}", replicas));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        kubernetes::scale_replicaset(&client, &namespace, &name, replicas).await
+    })
+    .await
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn scale_replication_controller(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    namespace: String,
+    name: String,
+    replicas: i32,
+    confirm: Option<String>,
+) -> Result<()> {
+    let action = AuditAction::new("scale", "ReplicationController", Some(&namespace), Some(&name))
+        .with_payload(format!("replicas={}", replicas));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        kubernetes::scale_replication_controller(&client, &namespace, &name, replicas).await
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn get_replicaset_detail(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<ReplicaSetDetail> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_replicaset_detail(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_replicaset_yaml(context_name: String, namespace: String, name: String) -> Result<String> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_replicaset_yaml(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<String> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_replicaset_yaml(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_replicaset_events(context_name: String, namespace: String, name: String) -> Result<Vec<ReplicaSetEvent>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_replicaset_events(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<Vec<ReplicaSetEvent>> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_replicaset_events(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_replicaset_pods(context_name: String, namespace: String, replicaset_name: String) -> Result<Vec<PodInfo>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_replicaset_pods(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, replicaset_name: String) -> Result<Vec<PodInfo>> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_replicaset_pods(&client, &namespace, &replicaset_name).await
 }
 
 // ============ NetworkPolicy Detail Commands ============
 
 #[tauri::command]
-pub async fn get_networkpolicy_detail(context_name: String, namespace: String, name: String) -> Result<NetworkPolicyDetail> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_networkpolicy_detail(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<NetworkPolicyDetail> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_networkpolicy_detail(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_networkpolicy_yaml(context_name: String, namespace: String, name: String) -> Result<String> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_networkpolicy_yaml(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<String> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_networkpolicy_yaml(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_networkpolicy_events(context_name: String, namespace: String, name: String) -> Result<Vec<NetworkPolicyEvent>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_networkpolicy_events(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<Vec<NetworkPolicyEvent>> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_networkpolicy_events(&client, &namespace, &name).await
 }
 
 // ============ HPA Detail Commands ============
 
 #[tauri::command]
-pub async fn get_hpa_detail(context_name: String, namespace: String, name: String) -> Result<HPADetail> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_hpa_detail(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<HPADetail> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_hpa_detail(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_hpa_yaml(context_name: String, namespace: String, name: String) -> Result<String> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_hpa_yaml(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<String> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_hpa_yaml(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_hpa_events(context_name: String, namespace: String, name: String) -> Result<Vec<HPAEvent>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_hpa_events(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<Vec<HPAEvent>> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_hpa_events(&client, &namespace, &name).await
 }
 
 // ============ PV Detail Commands ============
 
 #[tauri::command]
-pub async fn get_pv_detail(context_name: String, name: String) -> Result<PVDetail> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_pv_detail(context_manager: tauri::State<'_, ContextManager>, context_name: String, name: String) -> Result<PVDetail> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_pv_detail(&client, &name).await
 }
 
 #[tauri::command]
-pub async fn get_pv_yaml(context_name: String, name: String) -> Result<String> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_pv_yaml(context_manager: tauri::State<'_, ContextManager>, context_name: String, name: String) -> Result<String> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_pv_yaml(&client, &name).await
 }
 
 #[tauri::command]
-pub async fn get_pv_events(context_name: String, name: String) -> Result<Vec<PVEvent>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_pv_events(context_manager: tauri::State<'_, ContextManager>, context_name: String, name: String) -> Result<Vec<PVEvent>> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_pv_events(&client, &name).await
 }
 
 // ============ PVC Detail Commands ============
 
 #[tauri::command]
-pub async fn get_pvc_detail(context_name: String, namespace: String, name: String) -> Result<PVCDetail> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_pvc_detail(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<PVCDetail> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_pvc_detail(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_pvc_yaml(context_name: String, namespace: String, name: String) -> Result<String> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_pvc_yaml(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<String> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_pvc_yaml(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_pvc_events(context_name: String, namespace: String, name: String) -> Result<Vec<PVCEvent>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_pvc_events(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<Vec<PVCEvent>> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_pvc_events(&client, &namespace, &name).await
 }
 
 // ============ Namespace Detail Commands ============
 
 #[tauri::command]
-pub async fn get_namespace_detail(context_name: String, name: String) -> Result<NamespaceDetail> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_namespace_detail(context_manager: tauri::State<'_, ContextManager>, context_name: String, name: String) -> Result<NamespaceDetail> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_namespace_detail(&client, &name).await
 }
 
 #[tauri::command]
-pub async fn get_namespace_yaml(context_name: String, name: String) -> Result<String> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_namespace_yaml(context_manager: tauri::State<'_, ContextManager>, context_name: String, name: String) -> Result<String> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_namespace_yaml(&client, &name).await
 }
 
 #[tauri::command]
-pub async fn get_namespace_events(context_name: String, name: String) -> Result<Vec<NamespaceEvent>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_namespace_events(context_manager: tauri::State<'_, ContextManager>, context_name: String, name: String) -> Result<Vec<NamespaceEvent>> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_namespace_events(&client, &name).await
 }
 
 // ============ Node Detail Commands ============
 
 #[tauri::command]
-pub async fn get_node_detail(context_name: String, name: String) -> Result<NodeDetail> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_node_detail(context_manager: tauri::State<'_, ContextManager>, context_name: String, name: String) -> Result<NodeDetail> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_node_detail(&client, &name).await
 }
 
 #[tauri::command]
-pub async fn get_node_yaml(context_name: String, name: String) -> Result<String> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_node_yaml(context_manager: tauri::State<'_, ContextManager>, context_name: String, name: String) -> Result<String> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_node_yaml(&client, &name).await
 }
 
 #[tauri::command]
-pub async fn get_node_events(context_name: String, name: String) -> Result<Vec<NodeEvent>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_node_events(context_manager: tauri::State<'_, ContextManager>, context_name: String, name: String) -> Result<Vec<NodeEvent>> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_node_events(&client, &name).await
 }
 
 #[tauri::command]
-pub async fn get_node_pods(context_name: String, node_name: String) -> Result<Vec<PodInfo>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_node_pods(context_manager: tauri::State<'_, ContextManager>, context_name: String, node_name: String) -> Result<Vec<PodInfo>> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_node_pods(&client, &node_name).await
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn add_node_taint(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
     context_name: String,
     name: String,
     key: String,
     value: Option<String>,
     effect: String,
+    confirm: Option<String>,
 ) -> Result<()> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
-    kubernetes::add_node_taint(&client, &name, &key, value.as_deref(), &effect).await
+    let action = AuditAction::new("taint", "Node", None, Some(&name))
+        .with_payload(format!("{}={}:{}", key, value.as_deref().unwrap_or_default(), effect));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        kubernetes::add_node_taint(&client, &name, &key, value.as_deref(), &effect).await
+    })
+    .await
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn remove_node_taint(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
     context_name: String,
     name: String,
     key: String,
     effect: String,
+    confirm: Option<String>,
 ) -> Result<()> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
-    kubernetes::remove_node_taint(&client, &name, &key, &effect).await
+    let action = AuditAction::new("untaint", "Node", None, Some(&name))
+        .with_payload(format!("{}:{}", key, effect));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        kubernetes::remove_node_taint(&client, &name, &key, &effect).await
+    })
+    .await
 }
 
 #[tauri::command]
-pub async fn cordon_node(context_name: String, name: String) -> Result<()> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
-    kubernetes::cordon_node(&client, &name).await
+pub async fn cordon_node(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    name: String,
+    confirm: Option<String>,
+) -> Result<()> {
+    let action = AuditAction::new("cordon", "Node", None, Some(&name));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        kubernetes::cordon_node(&client, &name).await
+    })
+    .await
+}
+
+/// Cordon a node and evict its pods (except DaemonSet and mirror pods) through the
+/// Eviction API, retrying while PodDisruptionBudgets block them. The checks and
+/// cordon happen before returning; evictions continue in the background, and the
+/// drain is audited once they end.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn drain_node(
+    app: AppHandle,
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    watch_manager: tauri::State<'_, WatchManager>,
+    context_name: String,
+    name: String,
+    options: Option<DrainOptions>,
+    confirm: Option<String>,
+) -> Result<DrainStarted> {
+    let options = options.unwrap_or_default();
+    let action = AuditAction::new("drain", "Node", None, Some(&name)).with_payload(options.summary());
+    let started = async {
+        settings.ensure_mutation_allowed(&context_name, confirm.as_deref()).await?;
+        let client = context_manager.client_for_context(&context_name).await?;
+        let plan = drain::prepare(&client, &name, &options).await?;
+        if plan.evict.is_empty() {
+            return Ok(DrainStarted { drain_id: None, status: DrainStatus::Complete, plan });
+        }
+        let drain_id = watch_manager
+            .start_drain(app.clone(), client, context_name.clone(), action.clone(), plan.clone(), options.clone())
+            .await?;
+        Ok(DrainStarted { drain_id: Some(drain_id), status: DrainStatus::Running, plan })
+    }
+    .await;
+    // A running drain records its own entry when it ends
+    if !matches!(&started, Ok(DrainStarted { drain_id: Some(_), .. })) {
+        audit.record(&context_name, action, AuditOutcome::of(&started)).await;
+    }
+    started
 }
 
 #[tauri::command]
-pub async fn uncordon_node(context_name: String, name: String) -> Result<()> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
-    kubernetes::uncordon_node(&client, &name).await
+pub async fn uncordon_node(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    name: String,
+    confirm: Option<String>,
+) -> Result<()> {
+    let action = AuditAction::new("uncordon", "Node", None, Some(&name));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        kubernetes::uncordon_node(&client, &name).await
+    })
+    .await
 }
 
 // ============ ServiceAccount Detail Commands ============
 
 #[tauri::command]
-pub async fn get_serviceaccount_detail(context_name: String, namespace: String, name: String) -> Result<ServiceAccountDetail> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_serviceaccount_detail(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<ServiceAccountDetail> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_serviceaccount_detail(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_serviceaccount_yaml(context_name: String, namespace: String, name: String) -> Result<String> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_serviceaccount_yaml(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<String> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_serviceaccount_yaml(&client, &namespace, &name).await
 }
 
 #[tauri::command]
-pub async fn get_serviceaccount_events(context_name: String, namespace: String, name: String) -> Result<Vec<ServiceAccountEvent>> {
-    let client = kubernetes::create_client_for_context(&context_name).await?;
+pub async fn get_serviceaccount_events(context_manager: tauri::State<'_, ContextManager>, context_name: String, namespace: String, name: String) -> Result<Vec<ServiceAccountEvent>> {
+    let client = context_manager.client_for_context(&context_name).await?;
     kubernetes::get_serviceaccount_events(&client, &namespace, &name).await
 }
 
@@ -910,15 +1580,28 @@ pub async fn get_serviceaccount_events(context_name: String, namespace: String,
 use crate::pty::PtyManager;
 
 #[tauri::command]
-pub fn pty_spawn(
+#[allow(clippy::too_many_arguments)]
+pub async fn pty_spawn(
     app: AppHandle,
-    pty_manager: tauri::State<PtyManager>,
+    pty_manager: tauri::State<'_, PtyManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    context: Option<String>,
     namespace: String,
     pod_name: String,
     container: Option<String>,
     shell: Option<String>,
-) -> std::result::Result<String, String> {
-    pty_manager.spawn_session(app, &namespace, &pod_name, container.as_deref(), shell.as_deref())
+) -> Result<String> {
+    // An interactive shell in a pod can change anything the pod can, so
+    // read-only mode blocks it like port forwarding
+    settings.ensure_not_read_only().await?;
+    pty_manager.spawn_session(
+        app,
+        context.as_deref(),
+        &namespace,
+        &pod_name,
+        container.as_deref(),
+        shell.as_deref(),
+    )
 }
 
 #[tauri::command]
@@ -926,7 +1609,7 @@ pub fn pty_write(
     pty_manager: tauri::State<PtyManager>,
     session_id: String,
     data: String,
-) -> std::result::Result<(), String> {
+) -> Result<()> {
     pty_manager.write_to_session(&session_id, &data)
 }
 
@@ -936,7 +1619,7 @@ pub fn pty_resize(
     session_id: String,
     rows: u16,
     cols: u16,
-) -> std::result::Result<(), String> {
+) -> Result<()> {
     pty_manager.resize_session(&session_id, rows, cols)
 }
 
@@ -944,24 +1627,431 @@ pub fn pty_resize(
 pub fn pty_close(
     pty_manager: tauri::State<PtyManager>,
     session_id: String,
-) -> std::result::Result<(), String> {
+) -> Result<()> {
     pty_manager.close_session(&session_id)
 }
 
+// ============ Workload Commands ============
+
+/// Set a container's image on a Deployment, StatefulSet, DaemonSet, CronJob or Job.
+/// With `wait`, returns once the rollout finishes (or `timeout_seconds`, default
+/// 5 minutes, passes).
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn set_container_image(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    kind: WorkloadKind,
+    namespace: String,
+    name: String,
+    container: String,
+    image: String,
+    change_cause: Option<String>,
+    wait: Option<bool>,
+    timeout_seconds: Option<u64>,
+    confirm: Option<String>,
+) -> Result<SetImageResult> {
+    let action = AuditAction::new("set-image", kind.as_str(), Some(&namespace), Some(&name))
+        .with_payload(format!("{}={}", container, image));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        let mut result = workloads::set_image(
+            &client,
+            kind,
+            &namespace,
+            &name,
+            &container,
+            &image,
+            change_cause.as_deref(),
+        )
+        .await?;
+        if wait.unwrap_or(false) {
+            let timeout = std::time::Duration::from_secs(timeout_seconds.unwrap_or(300));
+            result.rollout = workloads::wait_for_rollout(&client, kind, &namespace, &name, timeout).await?;
+        }
+        Ok(result)
+    })
+    .await
+}
+
+/// Check a requests/limits change against the namespace's LimitRanges and
+/// ResourceQuotas and return the patch that would be sent, without applying it
+#[tauri::command]
+pub async fn preview_container_resources(
+    context_manager: tauri::State<'_, ContextManager>,
+    context_name: String,
+    kind: WorkloadKind,
+    namespace: String,
+    name: String,
+    container: String,
+    resources: ContainerResources,
+) -> Result<ResourcesPlan> {
+    let client = context_manager.client_for_context(&context_name).await?;
+    workloads::plan_resources(&client, kind, &namespace, &name, &container, &resources).await
+}
+
+/// Update a container's CPU/memory requests and limits. Values left unset are kept,
+/// empty strings remove them. Refused if the change breaks a LimitRange or quota.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn set_container_resources(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    kind: WorkloadKind,
+    namespace: String,
+    name: String,
+    container: String,
+    resources: ContainerResources,
+    confirm: Option<String>,
+) -> Result<ResourcesPlan> {
+    let action = AuditAction::new("set-resources", kind.as_str(), Some(&namespace), Some(&name))
+        .with_payload(format!("{}: {}", container, serde_json::to_string(&resources)?));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        workloads::set_resources(&client, kind, &namespace, &name, &container, &resources).await
+    })
+    .await
+}
+
+// ============ Rollout Commands ============
+
+/// Revisions of a Deployment (from its ReplicaSets) or a StatefulSet/DaemonSet
+/// (from its ControllerRevisions), oldest first
+#[tauri::command]
+pub async fn get_rollout_history(
+    context_manager: tauri::State<'_, ContextManager>,
+    context_name: String,
+    kind: RolloutKind,
+    namespace: String,
+    name: String,
+) -> Result<Vec<RolloutRevision>> {
+    let client = context_manager.client_for_context(&context_name).await?;
+    rollout::get_history(&client, kind, &namespace, &name).await
+}
+
+#[tauri::command]
+pub async fn diff_rollout_revisions(
+    context_manager: tauri::State<'_, ContextManager>,
+    context_name: String,
+    kind: RolloutKind,
+    namespace: String,
+    name: String,
+    from_revision: i64,
+    to_revision: i64,
+) -> Result<RevisionDiff> {
+    let client = context_manager.client_for_context(&context_name).await?;
+    rollout::diff_revisions(&client, kind, &namespace, &name, from_revision, to_revision).await
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn rollback_rollout(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    kind: RolloutKind,
+    namespace: String,
+    name: String,
+    revision: i64,
+    confirm: Option<String>,
+) -> Result<()> {
+    let action = AuditAction::new("rollback", kind.as_str(), Some(&namespace), Some(&name))
+        .with_payload(format!("revision={}", revision));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        rollout::rollback(&client, kind, &namespace, &name, revision).await
+    })
+    .await
+}
+
+// ============ Generic Resource Commands ============
+
+/// Delete objects of any served kind (including custom resources) by name, or in
+/// bulk by label selector. Returns one result per object.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn delete_resources(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: Option<String>,
+    api_version: Option<String>,
+    kind: String,
+    namespace: Option<String>,
+    names: Option<Vec<String>>,
+    label_selector: Option<String>,
+    all_namespaces: Option<bool>,
+    options: Option<DeleteOptions>,
+    confirm: Option<String>,
+) -> Result<Vec<DeleteResult>> {
+    let context_name = match context_name {
+        Some(name) => name,
+        None => context_manager.active_context().await?,
+    };
+    let names = names.unwrap_or_default();
+    let options = options.unwrap_or_default();
+    let all_namespaces = all_namespaces.unwrap_or(false);
+
+    let target = match (&label_selector, names.as_slice()) {
+        (Some(selector), _) if all_namespaces => format!("selector={} all-namespaces", selector),
+        (Some(selector), _) => format!("selector={}", selector),
+        (None, [_]) => String::new(),
+        (None, names) => format!("names={}", names.join(",")),
+    };
+    let payload = [target, options.summary()]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let single_name = match (&label_selector, names.as_slice()) {
+        (None, [name]) => Some(name.as_str()),
+        _ => None,
+    };
+    let action = AuditAction::new("delete", &kind, namespace.as_deref(), single_name).with_payload(payload);
+
+    run_audited(&settings, &audit, &context_name, confirm.as_deref(), action, AuditOutcome::of_objects, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        let resource = apply::Resolver::new(&context_manager, &context_name)
+            .await?
+            .find(api_version.as_deref(), &kind)
+            .await?;
+        resources::delete_resources(
+            &client,
+            &resource,
+            namespace.as_deref(),
+            &names,
+            label_selector.as_deref(),
+            all_namespaces,
+            &options,
+        )
+        .await
+    })
+    .await
+}
+
+/// Scale any kind that serves the `/scale` subresource, custom resources included
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn scale_resource(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    api_version: Option<String>,
+    kind: String,
+    namespace: Option<String>,
+    name: String,
+    replicas: i32,
+    confirm: Option<String>,
+) -> Result<ScaleInfo> {
+    let action = AuditAction::new("scale", &kind, namespace.as_deref(), Some(&name))
+        .with_payload(format!("replicas={}", replicas));
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        let resource = apply::Resolver::new(&context_manager, &context_name)
+            .await?
+            .find(api_version.as_deref(), &kind)
+            .await?;
+        resources::scale_resource(&client, &resource, namespace.as_deref(), &name, replicas).await
+    })
+    .await
+}
+
+/// Add, update or remove labels or annotations on objects of any kind, by name or
+/// in bulk by label selector. Returns one result per object.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn update_metadata(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    api_version: Option<String>,
+    kind: String,
+    namespace: Option<String>,
+    names: Option<Vec<String>>,
+    label_selector: Option<String>,
+    all_namespaces: Option<bool>,
+    field: MetadataField,
+    changes: MetadataChanges,
+    confirm: Option<String>,
+) -> Result<Vec<MetadataResult>> {
+    let names = names.unwrap_or_default();
+    let all_namespaces = all_namespaces.unwrap_or(false);
+
+    let verb = match field {
+        MetadataField::Labels => "label",
+        MetadataField::Annotations => "annotate",
+    };
+    let single_name = match (&label_selector, names.as_slice()) {
+        (None, [name]) => Some(name.as_str()),
+        _ => None,
+    };
+    let payload = match (&label_selector, single_name) {
+        (Some(selector), _) if all_namespaces => {
+            format!("selector={} all-namespaces {}", selector, changes.summary())
+        }
+        (Some(selector), _) => format!("selector={} {}", selector, changes.summary()),
+        (None, Some(_)) => changes.summary(),
+        (None, None) => format!("names={} {}", names.join(","), changes.summary()),
+    };
+    let action = AuditAction::new(verb, &kind, namespace.as_deref(), single_name).with_payload(payload);
+
+    run_audited(&settings, &audit, &context_name, confirm.as_deref(), action, AuditOutcome::of_objects, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        let resource = apply::Resolver::new(&context_manager, &context_name)
+            .await?
+            .find(api_version.as_deref(), &kind)
+            .await?;
+        resources::update_metadata(
+            &client,
+            &resource,
+            namespace.as_deref(),
+            &names,
+            label_selector.as_deref(),
+            all_namespaces,
+            field,
+            &changes,
+        )
+        .await
+    })
+    .await
+}
+
+/// Remove finalizers (all of them when `finalizers` is empty) from an object of any
+/// kind. Requires `acknowledge_risk`: whatever the finalizer guards is skipped.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn remove_finalizers(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
+    context_name: String,
+    api_version: Option<String>,
+    kind: String,
+    namespace: Option<String>,
+    name: String,
+    finalizers: Option<Vec<String>>,
+    acknowledge_risk: bool,
+    confirm: Option<String>,
+) -> Result<Vec<String>> {
+    if !acknowledge_risk {
+        return Err(AppError::Invalid(
+            "Removing finalizers skips their cleanup: acknowledge the risk to continue".to_string(),
+        ));
+    }
+    let finalizers = finalizers.unwrap_or_default();
+    let payload = if finalizers.is_empty() { "all".to_string() } else { finalizers.join(",") };
+    let action = AuditAction::new("remove-finalizers", &kind, namespace.as_deref(), Some(&name)).with_payload(payload);
+    run_mutation(&settings, &audit, &context_name, confirm.as_deref(), action, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        let resource = apply::Resolver::new(&context_manager, &context_name)
+            .await?
+            .find(api_version.as_deref(), &kind)
+            .await?;
+        resources::remove_finalizers(&client, &resource, namespace.as_deref(), &name, &finalizers).await
+    })
+    .await
+}
+
 // ============ YAML Apply Command ============
 
+/// Server-side apply a (multi-document) YAML manifest. With `force_conflicts`,
+/// fields owned by other managers are taken over instead of failing.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn apply_yaml(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
+    audit: tauri::State<'_, AuditLog>,
     context_name: String,
     yaml_content: String,
-) -> Result<String> {
-    kubernetes::apply_yaml(&context_name, &yaml_content).await
+    force_conflicts: Option<bool>,
+    confirm: Option<String>,
+) -> Result<Vec<ApplyResult>> {
+    let force_conflicts = force_conflicts.unwrap_or(false);
+    let action = apply_audit_action(&yaml_content, force_conflicts);
+    run_audited(&settings, &audit, &context_name, confirm.as_deref(), action, AuditOutcome::of_objects, async {
+        let client = context_manager.client_for_context(&context_name).await?;
+        let mut resolver = apply::Resolver::new(&context_manager, &context_name).await?;
+        apply::apply_yaml(&client, &mut resolver, &yaml_content, force_conflicts).await
+    })
+    .await
+}
+
+/// Dry-run apply a YAML manifest and diff each object against what's live.
+/// Nothing is persisted.
+#[tauri::command]
+pub async fn preview_apply_yaml(
+    context_manager: tauri::State<'_, ContextManager>,
+    context_name: String,
+    yaml_content: String,
+    force_conflicts: Option<bool>,
+) -> Result<Vec<ApplyPreview>> {
+    let client = context_manager.client_for_context(&context_name).await?;
+    let mut resolver = apply::Resolver::new(&context_manager, &context_name).await?;
+    apply::preview_apply(&client, &mut resolver, &yaml_content, force_conflicts.unwrap_or(false)).await
+}
+
+/// Describe an apply for the audit log from the manifest's documents. A single
+/// document is named; several are described by their kinds and, when they share
+/// one, their namespace. The applied objects are listed from the results.
+fn apply_audit_action(yaml_content: &str, force_conflicts: bool) -> AuditAction {
+    let documents: Vec<serde_yaml::Value> = serde_yaml::Deserializer::from_str(yaml_content)
+        .filter_map(|doc| <serde_yaml::Value as serde::Deserialize>::deserialize(doc).ok())
+        .filter(|doc| !doc.is_null())
+        .collect();
+    let field = |doc: &serde_yaml::Value, path: &[&str]| -> Option<String> {
+        let mut value = doc;
+        for key in path {
+            value = value.get(key)?;
+        }
+        value.as_str().map(str::to_string)
+    };
+    let distinct = |path: &[&str]| {
+        let mut values: Vec<Option<String>> = documents.iter().map(|doc| field(doc, path)).collect();
+        values.sort();
+        values.dedup();
+        values
+    };
+
+    let kinds = distinct(&["kind"]);
+    let kind = match kinds.as_slice() {
+        [] => "Unknown".to_string(),
+        kinds => kinds
+            .iter()
+            .map(|kind| kind.as_deref().unwrap_or("Unknown"))
+            .collect::<Vec<_>>()
+            .join(","),
+    };
+    let namespace = match distinct(&["metadata", "namespace"]).as_slice() {
+        [Some(namespace)] => Some(namespace.clone()),
+        _ => None,
+    };
+    let name = match documents.as_slice() {
+        [doc] => field(doc, &["metadata", "name"]),
+        _ => None,
+    };
+    AuditAction::new("apply", &kind, namespace.as_deref(), name.as_deref()).with_payload(format!(
+        "{} document(s), {} bytes{}",
+        documents.len(),
+        yaml_content.len(),
+        if force_conflicts { ", force-conflicts" } else { "" }
+    ))
 }
 
 // ============ Port Forward Commands ============
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn start_port_forward(
+    context_manager: tauri::State<'_, ContextManager>,
+    settings: tauri::State<'_, SettingsManager>,
     pf_manager: tauri::State<'_, PortForwardManager>,
     context: String,
     namespace: String,
@@ -970,7 +2060,10 @@ pub async fn start_port_forward(
     local_port: u16,
     remote_port: u16,
 ) -> Result<PortForwardInfo> {
-    pf_manager.start_forward(context, namespace, resource_type, resource_name, local_port, remote_port).await
+    // Port forwards open a path into the cluster network, so read-only mode blocks them too
+    settings.ensure_not_read_only().await?;
+    let client = context_manager.client_for_context(&context).await?;
+    pf_manager.start_forward(client, context, namespace, resource_type, resource_name, local_port, remote_port).await
 }
 
 #[tauri::command]
@@ -998,12 +2091,14 @@ pub async fn stop_all_port_forwards(
 
 #[tauri::command]
 pub async fn get_resource_ports(
+    context_manager: tauri::State<'_, ContextManager>,
     context: String,
     namespace: String,
     resource_type: ResourceType,
     resource_name: String,
 ) -> Result<Vec<AvailablePort>> {
-    portforward::get_resource_ports(&context, &namespace, &resource_type, &resource_name).await
+    let client = context_manager.client_for_context(&context).await?;
+    portforward::get_resource_ports(&client, &namespace, &resource_type, &resource_name).await
 }
 
 // ============ Watch Stream Commands ============
@@ -1011,45 +2106,92 @@ pub async fn get_resource_ports(
 #[tauri::command]
 pub async fn watch_pods(
     app: AppHandle,
+    context_manager: tauri::State<'_, ContextManager>,
     watch_manager: tauri::State<'_, WatchManager>,
     namespace: Option<String>,
 ) -> Result<String> {
-    watch_manager.start_pod_watch(app, namespace).await
+    let client = context_manager.create_client().await?;
+    watch_manager.start_pod_watch(app, client, namespace).await
 }
 
 #[tauri::command]
 pub async fn watch_deployments(
     app: AppHandle,
+    context_manager: tauri::State<'_, ContextManager>,
     watch_manager: tauri::State<'_, WatchManager>,
     namespace: Option<String>,
 ) -> Result<String> {
-    watch_manager.start_deployment_watch(app, namespace).await
+    let client = context_manager.create_client().await?;
+    watch_manager.start_deployment_watch(app, client, namespace).await
 }
 
 #[tauri::command]
 pub async fn watch_jobs(
     app: AppHandle,
+    context_manager: tauri::State<'_, ContextManager>,
     watch_manager: tauri::State<'_, WatchManager>,
     namespace: Option<String>,
 ) -> Result<String> {
-    watch_manager.start_job_watch(app, namespace).await
+    let client = context_manager.create_client().await?;
+    watch_manager.start_job_watch(app, client, namespace).await
 }
 
 #[tauri::command]
 pub async fn watch_nodes(
     app: AppHandle,
+    context_manager: tauri::State<'_, ContextManager>,
     watch_manager: tauri::State<'_, WatchManager>,
 ) -> Result<String> {
-    watch_manager.start_node_watch(app).await
+    let client = context_manager.create_client().await?;
+    watch_manager.start_node_watch(app, client).await
 }
 
 #[tauri::command]
 pub async fn watch_events(
     app: AppHandle,
+    context_manager: tauri::State<'_, ContextManager>,
     watch_manager: tauri::State<'_, WatchManager>,
     namespace: Option<String>,
 ) -> Result<String> {
-    watch_manager.start_event_watch(app, namespace).await
+    let client = context_manager.create_client().await?;
+    watch_manager.start_event_watch(app, client, namespace).await
+}
+
+/// Follow a rollout, e.g. after a restart or scale. Progress arrives as
+/// `rollout-status-<watch id>` events; the watch ends by itself once the rollout
+/// finishes. `timeout_seconds` defaults to 10 minutes.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn watch_rollout_status(
+    app: AppHandle,
+    context_manager: tauri::State<'_, ContextManager>,
+    watch_manager: tauri::State<'_, WatchManager>,
+    context_name: String,
+    kind: RolloutKind,
+    namespace: String,
+    name: String,
+    timeout_seconds: Option<u64>,
+) -> Result<RolloutWatch> {
+    let client = context_manager.client_for_context(&context_name).await?;
+    let status = rollout::current_status(&client, kind, &namespace, &name).await?;
+    if status.phase.is_finished() {
+        return Ok(RolloutWatch { watch_id: None, status });
+    }
+    let timeout = std::time::Duration::from_secs(timeout_seconds.unwrap_or(600));
+    let watch_id = watch_manager
+        .start_rollout_watch(app, client, status.clone(), timeout)
+        .await?;
+    Ok(RolloutWatch { watch_id: Some(watch_id), status })
+}
+
+/// Called once the frontend listens for a watch's events; watches that wait for
+/// it (rollouts, drains) start emitting
+#[tauri::command]
+pub async fn watch_listener_ready(
+    watch_manager: tauri::State<'_, WatchManager>,
+    watch_id: String,
+) -> Result<()> {
+    watch_manager.listener_ready(&watch_id).await
 }
 
 #[tauri::command]
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use kube::Client;
+use tokio::sync::RwLock;
+
+use crate::discovery::{self, ClusterApiInfo};
+use crate::error::{AppError, Result};
+use crate::kubernetes;
+
+/// Tracks the context the app is working against.
+///
+/// The active context lives in application state rather than in the kubeconfig
+/// `current-context`, so switching clusters in the app doesn't change the context
+/// of every other terminal on the machine. Until a context is picked in the app
+/// it follows the kubeconfig `current-context` (including external kubectx changes).
+///
+/// Clients are pooled per context, so commands reuse HTTP connections and the
+/// tokens obtained from exec auth plugins instead of re-reading kubeconfig and
+/// re-running the plugin on every call. A pooled client is rebuilt once any
+/// kubeconfig file has changed on disk, together with the API discovery cached
+/// for that context.
+pub struct ContextManager {
+    active: RwLock<Option<String>>,
+    clients: RwLock<HashMap<String, PooledClient>>,
+}
+
+struct PooledClient {
+    client: Client,
+    kubeconfig_mtimes: Vec<(PathBuf, Option<SystemTime>)>,
+    api_info: Option<Arc<ClusterApiInfo>>,
+}
+
+impl ContextManager {
+    pub fn new() -> Self {
+        Self {
+            active: RwLock::new(None),
+            clients: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// The context selected in the app, falling back to kubeconfig's `current-context`
+    pub async fn active_context(&self) -> Result<String> {
+        if let Some(name) = self.active.read().await.as_ref() {
+            return Ok(name.clone());
+        }
+        kubernetes::get_current_context_name().await
+    }
+
+    /// Select a context for the app. Only writes `current-context` to disk when
+    /// `persist` is set.
+    pub async fn set_active_context(&self, context_name: &str, persist: bool) -> Result<()> {
+        let names = kubernetes::get_context_names().await?;
+        if !names.iter().any(|n| n == context_name) {
+            return Err(AppError::NotFound(format!(
+                "Context '{}' not found",
+                context_name
+            )));
+        }
+
+        if persist {
+            kubernetes::switch_to_context(context_name).await?;
+        }
+
+        *self.active.write().await = Some(context_name.to_string());
+        Ok(())
+    }
+
+    /// Keep the active context pointing at a context that was renamed in kubeconfig
+    pub async fn context_renamed(&self, old_name: &str, new_name: &str) {
+        let mut active = self.active.write().await;
+        if active.as_deref() == Some(old_name) {
+            *active = Some(new_name.to_string());
+        }
+    }
+
+    /// Fall back to kubeconfig's `current-context` if the active context was deleted
+    pub async fn context_deleted(&self, context_name: &str) {
+        let mut active = self.active.write().await;
+        if active.as_deref() == Some(context_name) {
+            *active = None;
+        }
+    }
+
+    /// Client for the active context
+    pub async fn create_client(&self) -> Result<Client> {
+        let context_name = self.active_context().await?;
+        self.client_for_context(&context_name).await
+    }
+
+    /// Pooled client for a context, rebuilt if kubeconfig changed since it was created
+    pub async fn client_for_context(&self, context_name: &str) -> Result<Client> {
+        let mtimes = kubernetes::get_kubeconfig_mtimes();
+
+        if let Some(pooled) = self.clients.read().await.get(context_name) {
+            if pooled.kubeconfig_mtimes == mtimes {
+                return Ok(pooled.client.clone());
+            }
+        }
+
+        let client = kubernetes::create_client_for_context(context_name).await?;
+
+        let mut clients = self.clients.write().await;
+        // Clients built from an older kubeconfig are stale for every context
+        clients.retain(|_, pooled| pooled.kubeconfig_mtimes == mtimes);
+        clients.insert(
+            context_name.to_string(),
+            PooledClient {
+                client: client.clone(),
+                kubeconfig_mtimes: mtimes,
+                api_info: None,
+            },
+        );
+        Ok(client)
+    }
+
+    /// Server version and API discovery for a context, cached until `refresh` is set
+    /// or kubeconfig changes
+    pub async fn api_info(&self, context_name: &str, refresh: bool) -> Result<Arc<ClusterApiInfo>> {
+        let client = self.client_for_context(context_name).await?;
+
+        if !refresh {
+            let cached = self
+                .clients
+                .read()
+                .await
+                .get(context_name)
+                .and_then(|pooled| pooled.api_info.clone());
+            if let Some(info) = cached {
+                return Ok(info);
+            }
+        }
+
+        let info = Arc::new(discovery::discover(&client).await?);
+        if let Some(pooled) = self.clients.write().await.get_mut(context_name) {
+            pooled.api_info = Some(info.clone());
+        }
+        Ok(info)
+    }
+}
+
+impl Default for ContextManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
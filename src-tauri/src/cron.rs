@@ -0,0 +1,484 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use k8s_openapi::api::batch::v1::CronJob;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+use kube::api::Api;
+use kube::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, Result};
+use crate::kubernetes::get_age;
+
+/// The CronJob controller gives up on a schedule after this many missed runs
+const MAX_MISSED_RUNS: usize = 100;
+/// How often the CronJob controller re-evaluates schedules
+const CONTROLLER_SYNC_SECONDS: i64 = 10;
+/// Stop searching for the next run after this many years, like the controller's cron library
+const MAX_SEARCH_YEARS: i32 = 5;
+
+const MONTH_NAMES: [&str; 12] = ["JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC"];
+const DAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// A parsed CronJob schedule, following the standard 5-field syntax the CronJob
+/// controller accepts
+#[derive(Debug, Clone)]
+pub enum CronSchedule {
+    Fields(CronFields),
+    /// `@every <duration>`
+    Every(Duration),
+}
+
+/// Bitmasks of the allowed values for each field
+#[derive(Debug, Clone)]
+pub struct CronFields {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    /// Day fields given as `*` or `?` (step 1) don't restrict the other day field
+    day_of_month_any: bool,
+    day_of_week_any: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CronJobSchedule {
+    pub schedule: String,
+    /// Time zone the schedule is evaluated in
+    pub time_zone: String,
+    pub next_runs: Vec<String>,
+    pub last_schedule_time: Option<String>,
+    /// Most recent scheduled time that passed without a Job being started
+    pub last_missed: Option<String>,
+    pub since_last_missed: Option<String>,
+    /// Scheduled times missed since the last run (capped just above the controller's limit)
+    pub missed_runs: usize,
+    pub warnings: Vec<String>,
+}
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<Self> {
+        let expression = expression.trim();
+        if expression.starts_with("TZ=") || expression.starts_with("CRON_TZ=") {
+            return Err(AppError::Invalid(
+                "Time zones in the schedule are not supported: use spec.timeZone".to_string(),
+            ));
+        }
+
+        let expanded = match expression {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            _ => {
+                if let Some(duration) = expression.strip_prefix("@every ") {
+                    return Ok(CronSchedule::Every(parse_every(duration.trim())?));
+                }
+                if expression.starts_with('@') {
+                    return Err(AppError::Invalid(format!("Unknown schedule macro '{}'", expression)));
+                }
+                expression
+            }
+        };
+
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(AppError::Invalid(format!(
+                "Schedule '{}' must have 5 fields (minute hour day-of-month month day-of-week), got {}",
+                expression,
+                fields.len()
+            )));
+        }
+
+        Ok(CronSchedule::Fields(CronFields {
+            minutes: parse_field(fields[0], "minute", 0, 59, &[])?,
+            hours: parse_field(fields[1], "hour", 0, 23, &[])?,
+            days_of_month: parse_field(fields[2], "day of month", 1, 31, &[])?,
+            months: parse_field(fields[3], "month", 1, 12, &MONTH_NAMES)?,
+            days_of_week: parse_field(fields[4], "day of week", 0, 6, &DAY_NAMES)?,
+            day_of_month_any: is_unrestricted(fields[2]),
+            day_of_week_any: is_unrestricted(fields[4]),
+        }))
+    }
+
+    /// First scheduled time strictly after `after`, or `None` if there is none
+    /// within the search window (e.g. February 30th)
+    pub fn next_after(&self, after: DateTime<Tz>) -> Option<DateTime<Tz>> {
+        match self {
+            CronSchedule::Every(interval) => {
+                let next = after + *interval;
+                Some(next.with_nanosecond(0).unwrap_or(next))
+            }
+            CronSchedule::Fields(fields) => fields.next_after(after),
+        }
+    }
+}
+
+impl CronFields {
+    fn next_after(&self, after: DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let tz = after.timezone();
+        let start = after.date_naive();
+        let end = NaiveDate::from_ymd_opt(start.year() + MAX_SEARCH_YEARS, start.month(), 1)?;
+
+        for date in start.iter_days().take_while(|d| *d < end) {
+            if !self.day_matches(date) {
+                continue;
+            }
+            for hour in bits(self.hours) {
+                for minute in bits(self.minutes) {
+                    let Some(local) = date.and_hms_opt(hour, minute, 0) else {
+                        continue;
+                    };
+                    // Times skipped by a DST change don't run; repeated times run once
+                    let Some(time) = tz.from_local_datetime(&local).earliest() else {
+                        continue;
+                    };
+                    if time > after {
+                        return Some(time);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        if !has_bit(self.months, date.month()) {
+            return false;
+        }
+        let day_of_month = has_bit(self.days_of_month, date.day());
+        let day_of_week = has_bit(self.days_of_week, date.weekday().num_days_from_sunday());
+        // Like cron, two restricted day fields match on either
+        if self.day_of_month_any || self.day_of_week_any {
+            day_of_month && day_of_week
+        } else {
+            day_of_month || day_of_week
+        }
+    }
+}
+
+/// Work out upcoming and missed runs of a CronJob and warn about settings that
+/// will cause runs to be skipped
+pub async fn get_cronjob_schedule(client: &Client, namespace: &str, name: &str, count: usize) -> Result<CronJobSchedule> {
+    let cronjobs: Api<CronJob> = Api::namespaced(client.clone(), namespace);
+    let cj = cronjobs.get(name).await?;
+    let spec = cj.spec.unwrap_or_default();
+    let status = cj.status.unwrap_or_default();
+
+    let schedule = CronSchedule::parse(&spec.schedule)?;
+    let tz: Tz = match spec.time_zone.as_deref() {
+        Some(name) => name
+            .parse()
+            .map_err(|_| AppError::Invalid(format!("Unknown time zone '{}'", name)))?,
+        None => Tz::UTC,
+    };
+
+    let now = Utc::now().with_timezone(&tz);
+    let mut warnings = Vec::new();
+    if spec.time_zone.is_none() {
+        warnings.push(
+            "No timeZone set: runs follow the kube-controller-manager's local time zone, assumed UTC here".to_string(),
+        );
+    }
+
+    let mut next_runs = Vec::new();
+    let mut cursor = now;
+    while next_runs.len() < count {
+        let Some(next) = schedule.next_after(cursor) else {
+            break;
+        };
+        next_runs.push(next);
+        cursor = next;
+    }
+    if next_runs.is_empty() {
+        warnings.push(format!(
+            "Schedule '{}' never runs within the next {} years",
+            spec.schedule, MAX_SEARCH_YEARS
+        ));
+    }
+
+    // Scheduled times between the last run (or creation) and now that didn't start a Job
+    let last_schedule = status.last_schedule_time.as_ref().map(|t| t.0);
+    let mut missed = Vec::new();
+    if let Some(since) = last_schedule.or(cj.metadata.creation_timestamp.as_ref().map(|t| t.0)) {
+        let mut cursor = since.with_timezone(&tz);
+        while let Some(next) = schedule.next_after(cursor) {
+            if next > now || missed.len() > MAX_MISSED_RUNS {
+                break;
+            }
+            missed.push(next);
+            cursor = next;
+        }
+    }
+    let last_missed = missed.last().copied();
+
+    if spec.suspend.unwrap_or(false) {
+        warnings.push("Suspended: no Jobs are created until the CronJob is resumed".to_string());
+    }
+    if missed.len() > MAX_MISSED_RUNS {
+        warnings.push(format!(
+            "More than {} runs were missed; the controller will only start the most recent one",
+            MAX_MISSED_RUNS
+        ));
+    }
+
+    if let Some(deadline) = spec.starting_deadline_seconds {
+        if deadline < CONTROLLER_SYNC_SECONDS {
+            warnings.push(format!(
+                "startingDeadlineSeconds is {}s but the controller only checks every {}s: runs may be skipped",
+                deadline, CONTROLLER_SYNC_SECONDS
+            ));
+        }
+        if let Some(missed_at) = last_missed {
+            if now.signed_duration_since(missed_at).num_seconds() > deadline && !spec.suspend.unwrap_or(false) {
+                warnings.push(format!(
+                    "The run scheduled at {} is past its {}s starting deadline and will be skipped",
+                    missed_at.to_rfc3339(),
+                    deadline
+                ));
+            }
+        }
+    }
+
+    let active = status.active.as_ref().map(|a| a.len()).unwrap_or(0);
+    let policy = spec.concurrency_policy.as_deref().unwrap_or("Allow");
+    let active_deadline = spec
+        .job_template
+        .spec
+        .as_ref()
+        .and_then(|s| s.active_deadline_seconds);
+    let shortest_interval = next_runs
+        .windows(2)
+        .map(|w| w[1].signed_duration_since(w[0]).num_seconds())
+        .min();
+    match policy {
+        "Forbid" => {
+            if active > 0 {
+                if let Some(next) = next_runs.first() {
+                    warnings.push(format!(
+                        "concurrencyPolicy is Forbid and {} Job(s) are running: the run at {} is skipped if they haven't finished",
+                        active,
+                        next.to_rfc3339()
+                    ));
+                }
+            }
+            if let (Some(deadline), Some(interval)) = (active_deadline, shortest_interval) {
+                if deadline > interval {
+                    warnings.push(format!(
+                        "Jobs may run for up to {}s but runs are {}s apart: with Forbid, overlapping runs are skipped",
+                        deadline, interval
+                    ));
+                }
+            }
+        }
+        "Replace" if active > 0 => {
+            warnings.push(format!(
+                "concurrencyPolicy is Replace: the next run deletes the {} running Job(s)",
+                active
+            ));
+        }
+        _ => {}
+    }
+
+    Ok(CronJobSchedule {
+        schedule: spec.schedule,
+        time_zone: tz.name().to_string(),
+        next_runs: next_runs.iter().map(|t| t.to_rfc3339()).collect(),
+        last_schedule_time: last_schedule.map(|t| t.to_rfc3339()),
+        last_missed: last_missed.map(|t| t.to_rfc3339()),
+        since_last_missed: last_missed.map(|t| get_age(Some(&Time(t.with_timezone(&Utc))))),
+        missed_runs: missed.len(),
+        warnings,
+    })
+}
+
+/// Whether a day field counts as `*` when combining the two day fields. As in the
+/// controller's parser, a star with a step above 1 (`*/2`) is a restriction.
+fn is_unrestricted(field: &str) -> bool {
+    field.split(',').any(|part| {
+        let (base, step) = part.split_once('/').unwrap_or((part, "1"));
+        matches!(base, "*" | "?") && step.parse::<u32>() == Ok(1)
+    })
+}
+
+/// Parse one field: `*`, `?`, values, names, `a-b` ranges and `/step`, comma separated
+fn parse_field(field: &str, label: &str, min: u32, max: u32, names: &[&str]) -> Result<u64> {
+    let invalid = |reason: String| AppError::Invalid(format!("Invalid {} field '{}': {}", label, field, reason));
+    let value = |s: &str| -> Result<u32> {
+        if let Some(index) = names.iter().position(|n| n.eq_ignore_ascii_case(s)) {
+            return Ok(index as u32 + min);
+        }
+        let n: u32 = s.parse().map_err(|_| invalid(format!("'{}' is not a number", s)))?;
+        if n < min || n > max {
+            return Err(invalid(format!("{} is outside {}-{}", n, min, max)));
+        }
+        Ok(n)
+    };
+
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse().map_err(|_| invalid(format!("bad step '{}'", step)))?;
+                if step == 0 {
+                    return Err(invalid("step must be positive".to_string()));
+                }
+                (range, Some(step))
+            }
+            None => (part, None),
+        };
+        let (start, end) = match range {
+            "*" | "?" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (value(start)?, value(end)?),
+                // `a/n` means every n-th value starting at a
+                None if step.is_some() => (value(range)?, max),
+                None => {
+                    let v = value(range)?;
+                    (v, v)
+                }
+            },
+        };
+        if start > end {
+            return Err(invalid(format!("range {}-{} is backwards", start, end)));
+        }
+        for v in (start..=end).step_by(step.unwrap_or(1) as usize) {
+            mask |= 1 << v;
+        }
+    }
+    Ok(mask)
+}
+
+/// Parse the duration of `@every`, e.g. `1h30m`, `90s`
+fn parse_every(duration: &str) -> Result<Duration> {
+    let invalid = || AppError::Invalid(format!("Invalid @every duration '{}' (e.g. 1h30m, 45s)", duration));
+    let unit_regex = regex::Regex::new(r"([0-9]+)(h|m|s)").unwrap();
+    let mut total = Duration::zero();
+    let mut consumed = 0;
+    for captures in unit_regex.captures_iter(duration) {
+        let whole = captures.get(0).ok_or_else(invalid)?;
+        if whole.start() != consumed {
+            return Err(invalid());
+        }
+        consumed = whole.end();
+        let n: i64 = captures[1].parse().map_err(|_| invalid())?;
+        total += match &captures[2] {
+            "h" => Duration::hours(n),
+            "m" => Duration::minutes(n),
+            _ => Duration::seconds(n),
+        };
+    }
+    if consumed != duration.len() || total < Duration::seconds(1) {
+        return Err(invalid());
+    }
+    Ok(total)
+}
+
+fn has_bit(mask: u64, bit: u32) -> bool {
+    mask & (1 << bit) != 0
+}
+
+fn bits(mask: u64) -> impl Iterator<Item = u32> {
+    (0..64).filter(move |b| has_bit(mask, *b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+
+    fn at(tz: Tz, local: &str) -> DateTime<Tz> {
+        let naive = NaiveDateTime::parse_from_str(local, "%Y-%m-%d %H:%M").unwrap();
+        tz.from_local_datetime(&naive).single().unwrap()
+    }
+
+    fn next(schedule: &str, tz: Tz, after: &str) -> DateTime<Tz> {
+        CronSchedule::parse(schedule).unwrap().next_after(at(tz, after)).unwrap()
+    }
+
+    fn minutes(field: &str) -> Vec<u32> {
+        bits(parse_field(field, "minute", 0, 59, &[]).unwrap()).collect()
+    }
+
+    #[test]
+    fn macros_expand_to_fields() {
+        // 2024-01-03 is a Wednesday
+        let after = "2024-01-03 12:34";
+        assert_eq!(next("@hourly", Tz::UTC, after), at(Tz::UTC, "2024-01-03 13:00"));
+        assert_eq!(next("@daily", Tz::UTC, after), at(Tz::UTC, "2024-01-04 00:00"));
+        assert_eq!(next("@midnight", Tz::UTC, after), at(Tz::UTC, "2024-01-04 00:00"));
+        assert_eq!(next("@weekly", Tz::UTC, after), at(Tz::UTC, "2024-01-07 00:00"));
+        assert_eq!(next("@monthly", Tz::UTC, after), at(Tz::UTC, "2024-02-01 00:00"));
+        assert_eq!(next("@yearly", Tz::UTC, after), at(Tz::UTC, "2025-01-01 00:00"));
+        assert_eq!(next("@annually", Tz::UTC, after), at(Tz::UTC, "2025-01-01 00:00"));
+        assert!(CronSchedule::parse("@fortnightly").is_err());
+    }
+
+    #[test]
+    fn every_adds_the_interval() {
+        assert_eq!(next("@every 1h30m", Tz::UTC, "2024-01-03 12:00"), at(Tz::UTC, "2024-01-03 13:30"));
+        assert_eq!(next("@every 90s", Tz::UTC, "2024-01-03 12:00").timestamp(), at(Tz::UTC, "2024-01-03 12:01").timestamp() + 30);
+        for invalid in ["", "0s", "1x", "1h 30m", "h"] {
+            assert!(parse_every(invalid).is_err(), "{:?} should be rejected", invalid);
+        }
+    }
+
+    #[test]
+    fn restricted_day_fields_match_on_either() {
+        // 2024-01-01 is a Monday; the first Friday is the 5th
+        assert_eq!(next("0 0 13 * 5", Tz::UTC, "2024-01-01 00:00"), at(Tz::UTC, "2024-01-05 00:00"));
+        // One day field left as `*` or `?` only restricts by the other
+        assert_eq!(next("0 0 13 * *", Tz::UTC, "2024-01-01 00:00"), at(Tz::UTC, "2024-01-13 00:00"));
+        assert_eq!(next("0 0 ? * 5", Tz::UTC, "2024-01-01 00:00"), at(Tz::UTC, "2024-01-05 00:00"));
+        assert_eq!(next("0 0 13 * ?", Tz::UTC, "2024-01-01 00:00"), at(Tz::UTC, "2024-01-13 00:00"));
+    }
+
+    #[test]
+    fn stepped_stars_restrict_day_fields() {
+        assert!(is_unrestricted("*"));
+        assert!(is_unrestricted("?"));
+        assert!(is_unrestricted("*/1"));
+        assert!(is_unrestricted("1,*"));
+        assert!(!is_unrestricted("*/2"));
+        assert!(!is_unrestricted("1-31"));
+
+        // `*/2` (odd days) is a restriction, so Mondays OR odd days: the 3rd comes first
+        assert_eq!(next("0 0 */2 * 1", Tz::UTC, "2024-01-01 00:00"), at(Tz::UTC, "2024-01-03 00:00"));
+        // `*/1` is still a star, so only Mondays match
+        assert_eq!(next("0 0 */1 * 1", Tz::UTC, "2024-01-01 00:00"), at(Tz::UTC, "2024-01-08 00:00"));
+    }
+
+    #[test]
+    fn ranges_steps_and_names() {
+        assert_eq!(minutes("5-20/5"), vec![5, 10, 15, 20]);
+        assert_eq!(minutes("10/15"), vec![10, 25, 40, 55]);
+        assert_eq!(minutes("1,2,30-31"), vec![1, 2, 30, 31]);
+        assert_eq!(minutes("*/20"), vec![0, 20, 40]);
+
+        let weekdays = parse_field("MON-FRI", "day of week", 0, 6, &DAY_NAMES).unwrap();
+        assert_eq!(bits(weekdays).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+        let months = parse_field("jan,Jul", "month", 1, 12, &MONTH_NAMES).unwrap();
+        assert_eq!(bits(months).collect::<Vec<_>>(), vec![1, 7]);
+
+        for invalid in ["5-1", "*/0", "60", "a", "1-"] {
+            assert!(parse_field(invalid, "minute", 0, 59, &[]).is_err(), "{:?} should be rejected", invalid);
+        }
+        assert!(CronSchedule::parse("0 0 * *").is_err());
+        assert!(CronSchedule::parse("CRON_TZ=UTC 0 0 * * *").is_err());
+        assert!(CronSchedule::parse("0 0 31 2 *").unwrap().next_after(at(Tz::UTC, "2024-01-01 00:00")).is_none());
+    }
+
+    #[test]
+    fn dst_gaps_are_skipped_and_repeats_run_once() {
+        let tz: Tz = "America/New_York".parse().unwrap();
+        // 02:30 doesn't exist on 2024-03-10, when clocks jump from 02:00 to 03:00
+        assert_eq!(next("30 2 * * *", tz, "2024-03-09 03:00"), at(tz, "2024-03-11 02:30"));
+
+        // 01:30 happens twice on 2024-11-03; only the first (EDT) one runs
+        let first = next("30 1 * * *", tz, "2024-11-03 00:00");
+        assert_eq!(first.with_timezone(&Utc), Utc.with_ymd_and_hms(2024, 11, 3, 5, 30, 0).unwrap());
+        let second = CronSchedule::parse("30 1 * * *").unwrap().next_after(first).unwrap();
+        assert_eq!(second, at(tz, "2024-11-04 01:30"));
+    }
+}
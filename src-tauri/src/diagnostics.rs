@@ -0,0 +1,448 @@
+use std::error::Error as StdError;
+use std::time::{Duration, Instant};
+
+use k8s_openapi::api::core::v1::Namespace;
+use kube::api::ListParams;
+use kube::config::{AuthInfo, KubeConfigOptions};
+use kube::{Api, Client, Config};
+use serde::{Deserialize, Serialize};
+
+use crate::kubernetes::read_kubeconfig;
+
+const NETWORK_TIMEOUT: Duration = Duration::from_secs(10);
+const EXEC_PLUGIN_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionDiagnostics {
+    pub context: String,
+    pub server: Option<String>,
+    pub success: bool,
+    pub server_version: Option<String>,
+    pub namespace_count: Option<usize>,
+    pub phases: Vec<DiagnosticPhase>,
+    pub failure: Option<ConnectionFailure>,
+    pub total_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticPhase {
+    pub name: String,
+    pub status: PhaseStatus,
+    pub duration_ms: Option<u64>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PhaseStatus {
+    Ok,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionFailure {
+    pub kind: FailureKind,
+    pub phase: String,
+    pub message: String,
+    pub hint: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureKind {
+    InvalidConfig,
+    Unreachable,
+    CertificateError,
+    ExpiredToken,
+    ExecPluginMissing,
+    ExecPluginFailed,
+    Forbidden,
+    Unknown,
+}
+
+impl FailureKind {
+    fn hint(&self) -> &'static str {
+        match self {
+            FailureKind::InvalidConfig => "Check the context's cluster and user entries in kubeconfig",
+            FailureKind::Unreachable => "Check the server address, VPN and proxy settings, and that the cluster is running",
+            FailureKind::CertificateError => "The server certificate is not trusted: check certificate-authority-data or the server name",
+            FailureKind::ExpiredToken => "Credentials were rejected: log in again or refresh the token",
+            FailureKind::ExecPluginMissing => "Install the credential plugin or add it to PATH",
+            FailureKind::ExecPluginFailed => "Run the credential plugin manually to see why it fails",
+            FailureKind::Forbidden => "Authenticated, but RBAC does not allow this: ask for list access to namespaces",
+            FailureKind::Unknown => "See the error message for details",
+        }
+    }
+}
+
+/// Records phases as they run and stops at the first failure
+struct Report {
+    diagnostics: ConnectionDiagnostics,
+    started: Instant,
+}
+
+impl Report {
+    fn new(context: &str) -> Self {
+        Self {
+            diagnostics: ConnectionDiagnostics {
+                context: context.to_string(),
+                server: None,
+                success: false,
+                server_version: None,
+                namespace_count: None,
+                phases: Vec::new(),
+                failure: None,
+                total_ms: 0,
+            },
+            started: Instant::now(),
+        }
+    }
+
+    fn ok(&mut self, name: &str, started: Instant, message: Option<String>) {
+        self.push(name, PhaseStatus::Ok, Some(started), message);
+    }
+
+    fn skipped(&mut self, name: &str, message: &str) {
+        self.push(name, PhaseStatus::Skipped, None, Some(message.to_string()));
+    }
+
+    fn fail(mut self, name: &str, started: Instant, kind: FailureKind, message: String) -> ConnectionDiagnostics {
+        self.push(name, PhaseStatus::Failed, Some(started), Some(message.clone()));
+        self.diagnostics.failure = Some(ConnectionFailure {
+            kind,
+            phase: name.to_string(),
+            message,
+            hint: kind.hint().to_string(),
+        });
+        self.finish()
+    }
+
+    fn push(&mut self, name: &str, status: PhaseStatus, started: Option<Instant>, message: Option<String>) {
+        self.diagnostics.phases.push(DiagnosticPhase {
+            name: name.to_string(),
+            status,
+            duration_ms: started.map(|s| s.elapsed().as_millis() as u64),
+            message,
+        });
+    }
+
+    fn finish(mut self) -> ConnectionDiagnostics {
+        self.diagnostics.success = self.diagnostics.failure.is_none();
+        self.diagnostics.total_ms = self.started.elapsed().as_millis() as u64;
+        self.diagnostics
+    }
+}
+
+/// Check that a context can actually reach and use its cluster.
+///
+/// Runs config, DNS, TCP connect, TLS, auth plugin, `/version` and namespace list
+/// phases in order, timing each one and stopping at the first failure. Failures
+/// are reported in the result rather than as an error so the startup screen can
+/// show which phase broke and why.
+pub async fn diagnose_connection(context_name: &str) -> ConnectionDiagnostics {
+    let mut report = Report::new(context_name);
+
+    // Config
+    let started = Instant::now();
+    let config = match load_config(context_name).await {
+        Ok(config) => config,
+        Err(e) => return report.fail("config", started, FailureKind::InvalidConfig, e),
+    };
+    report.diagnostics.server = Some(config.cluster_url.to_string());
+    report.ok("config", started, None);
+
+    let host = config.cluster_url.host().unwrap_or_default().to_string();
+    let https = config.cluster_url.scheme_str() != Some("http");
+    let port = config
+        .cluster_url
+        .port_u16()
+        .unwrap_or(if https { 443 } else { 80 });
+
+    if config.proxy_url.is_some() {
+        report.skipped("dns", "Connecting through a proxy");
+        report.skipped("connect", "Connecting through a proxy");
+    } else {
+        // DNS
+        let started = Instant::now();
+        let addrs: Vec<_> = match tokio::time::timeout(
+            NETWORK_TIMEOUT,
+            tokio::net::lookup_host((host.as_str(), port)),
+        )
+        .await
+        {
+            Ok(Ok(addrs)) => addrs.collect(),
+            Ok(Err(e)) => {
+                let message = format!("Failed to resolve {}: {}", host, e);
+                return report.fail("dns", started, FailureKind::Unreachable, message);
+            }
+            Err(_) => {
+                let message = format!("Timed out resolving {}", host);
+                return report.fail("dns", started, FailureKind::Unreachable, message);
+            }
+        };
+        let Some(addr) = addrs.first().copied() else {
+            let message = format!("{} did not resolve to any address", host);
+            return report.fail("dns", started, FailureKind::Unreachable, message);
+        };
+        report.ok("dns", started, Some(addr.ip().to_string()));
+
+        // TCP connect
+        let started = Instant::now();
+        match tokio::time::timeout(NETWORK_TIMEOUT, tokio::net::TcpStream::connect(addr)).await {
+            Ok(Ok(_)) => report.ok("connect", started, Some(addr.to_string())),
+            Ok(Err(e)) => {
+                let message = format!("Failed to connect to {}: {}", addr, e);
+                return report.fail("connect", started, FailureKind::Unreachable, message);
+            }
+            Err(_) => {
+                let message = format!("Timed out connecting to {}", addr);
+                return report.fail("connect", started, FailureKind::Unreachable, message);
+            }
+        }
+    }
+
+    // TLS: an unauthenticated request on a fresh connection, so certificate problems
+    // are told apart from credential problems. The timing includes one round trip.
+    if https {
+        let started = Instant::now();
+        let mut anonymous = config.clone();
+        anonymous.auth_info = AuthInfo::default();
+        let result = match Client::try_from(anonymous) {
+            Ok(client) => timed(client.apiserver_version()).await,
+            Err(e) => Err(TimedError::Kube(e)),
+        };
+        match result {
+            // Any HTTP response (including 401/403) means the handshake succeeded
+            Ok(_) | Err(TimedError::Kube(kube::Error::Api(_))) => report.ok("tls", started, None),
+            Err(e) => {
+                let (kind, message) = e.classify();
+                return report.fail("tls", started, kind, message);
+            }
+        }
+    } else {
+        report.skipped("tls", "Server uses plain HTTP");
+    }
+
+    // Auth plugin
+    let started = Instant::now();
+    match run_exec_plugin(&config.auth_info).await {
+        ExecOutcome::NotConfigured(kind) => report.skipped("auth", &kind),
+        ExecOutcome::Ok(message) => report.ok("auth", started, Some(message)),
+        ExecOutcome::Failed(kind, message) => return report.fail("auth", started, kind, message),
+    }
+
+    // Authenticated request
+    let started = Instant::now();
+    let client = match Client::try_from(config) {
+        Ok(client) => client,
+        Err(e) => {
+            let (kind, message) = TimedError::Kube(e).classify();
+            return report.fail("request", started, kind, message);
+        }
+    };
+    match timed(client.apiserver_version()).await {
+        Ok(info) => {
+            report.diagnostics.server_version = Some(info.git_version.clone());
+            report.ok("request", started, Some(format!("Server {}", info.git_version)));
+        }
+        Err(e) => {
+            let (kind, message) = e.classify();
+            return report.fail("request", started, kind, message);
+        }
+    }
+
+    // Namespace list
+    let started = Instant::now();
+    let namespaces: Api<Namespace> = Api::all(client);
+    match timed(namespaces.list(&ListParams::default())).await {
+        Ok(list) => {
+            report.diagnostics.namespace_count = Some(list.items.len());
+            report.ok("namespaces", started, Some(format!("{} namespaces", list.items.len())));
+        }
+        Err(e) => {
+            let (kind, message) = e.classify();
+            return report.fail("namespaces", started, kind, message);
+        }
+    }
+
+    report.finish()
+}
+
+async fn load_config(context_name: &str) -> std::result::Result<Config, String> {
+    let kubeconfig = read_kubeconfig().map_err(|e| e.to_string())?;
+    let options = KubeConfigOptions {
+        context: Some(context_name.to_string()),
+        ..Default::default()
+    };
+    Config::from_custom_kubeconfig(kubeconfig, &options)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+enum TimedError {
+    Kube(kube::Error),
+    Timeout,
+}
+
+impl TimedError {
+    fn classify(&self) -> (FailureKind, String) {
+        let e = match self {
+            TimedError::Timeout => {
+                return (
+                    FailureKind::Unreachable,
+                    format!("No response within {}s", NETWORK_TIMEOUT.as_secs()),
+                )
+            }
+            TimedError::Kube(e) => e,
+        };
+
+        let message = error_chain(e);
+        let kind = match e {
+            kube::Error::Api(response) if response.code == 401 => FailureKind::ExpiredToken,
+            kube::Error::Api(response) if response.code == 403 => FailureKind::Forbidden,
+            kube::Error::Auth(kube::client::AuthError::AuthExecStart(io))
+                if io.kind() == std::io::ErrorKind::NotFound =>
+            {
+                FailureKind::ExecPluginMissing
+            }
+            kube::Error::Auth(_) => FailureKind::ExecPluginFailed,
+            _ if is_certificate_error(&message) => FailureKind::CertificateError,
+            kube::Error::HyperError(_) | kube::Error::Service(_) => FailureKind::Unreachable,
+            _ => FailureKind::Unknown,
+        };
+        (kind, message)
+    }
+}
+
+async fn timed<T>(
+    fut: impl std::future::Future<Output = std::result::Result<T, kube::Error>>,
+) -> std::result::Result<T, TimedError> {
+    match tokio::time::timeout(NETWORK_TIMEOUT, fut).await {
+        Ok(result) => result.map_err(TimedError::Kube),
+        Err(_) => Err(TimedError::Timeout),
+    }
+}
+
+/// Join an error with its sources, since TLS failures are usually buried a few levels down
+fn error_chain(e: &dyn StdError) -> String {
+    let mut message = e.to_string();
+    let mut source = e.source();
+    while let Some(inner) = source {
+        let text = inner.to_string();
+        if !message.contains(&text) {
+            message.push_str(": ");
+            message.push_str(&text);
+        }
+        source = inner.source();
+    }
+    message
+}
+
+fn is_certificate_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    ["certificate", "unknownissuer", "unknown issuer", "tls handshake", "invalid peer"]
+        .iter()
+        .any(|needle| message.contains(needle))
+}
+
+enum ExecOutcome {
+    NotConfigured(String),
+    Ok(String),
+    Failed(FailureKind, String),
+}
+
+/// Run the exec credential plugin ourselves so its time and failures show up as
+/// a separate phase instead of being folded into the first request
+async fn run_exec_plugin(auth_info: &AuthInfo) -> ExecOutcome {
+    let Some(exec) = &auth_info.exec else {
+        let kind = if auth_info.client_certificate.is_some() || auth_info.client_certificate_data.is_some() {
+            "Client certificate"
+        } else if auth_info.token.is_some() || auth_info.token_file.is_some() {
+            "Bearer token"
+        } else if auth_info.auth_provider.is_some() {
+            "Auth provider"
+        } else if auth_info.username.is_some() {
+            "Basic auth"
+        } else {
+            "No credentials"
+        };
+        return ExecOutcome::NotConfigured(kind.to_string());
+    };
+
+    let Some(command) = &exec.command else {
+        return ExecOutcome::Failed(FailureKind::InvalidConfig, "Exec plugin has no command".to_string());
+    };
+
+    let mut cmd = tokio::process::Command::new(command);
+    cmd.args(exec.args.iter().flatten());
+    for env in exec.env.iter().flatten() {
+        if let (Some(name), Some(value)) = (env.get("name"), env.get("value")) {
+            cmd.env(name, value);
+        }
+    }
+    cmd.env(
+        "KUBERNETES_EXEC_INFO",
+        serde_json::json!({
+            "apiVersion": exec.api_version.clone().unwrap_or_default(),
+            "kind": "ExecCredential",
+            "spec": { "interactive": false },
+        })
+        .to_string(),
+    );
+    cmd.stdin(std::process::Stdio::null());
+    cmd.kill_on_drop(true);
+
+    let output = match tokio::time::timeout(EXEC_PLUGIN_TIMEOUT, cmd.output()).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) if e.kind() == std::io::ErrorKind::NotFound => {
+            return ExecOutcome::Failed(
+                FailureKind::ExecPluginMissing,
+                format!("Credential plugin '{}' not found", command),
+            );
+        }
+        Ok(Err(e)) => {
+            return ExecOutcome::Failed(
+                FailureKind::ExecPluginFailed,
+                format!("Failed to run '{}': {}", command, e),
+            );
+        }
+        Err(_) => {
+            return ExecOutcome::Failed(
+                FailureKind::ExecPluginFailed,
+                format!("'{}' did not finish within {}s", command, EXEC_PLUGIN_TIMEOUT.as_secs()),
+            );
+        }
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return ExecOutcome::Failed(
+            FailureKind::ExecPluginFailed,
+            format!("'{}' exited with {}: {}", command, output.status, stderr),
+        );
+    }
+
+    let credential: serde_json::Value = match serde_json::from_slice(&output.stdout) {
+        Ok(value) => value,
+        Err(e) => {
+            return ExecOutcome::Failed(
+                FailureKind::ExecPluginFailed,
+                format!("'{}' returned an invalid ExecCredential: {}", command, e),
+            );
+        }
+    };
+
+    let expiration = credential
+        .pointer("/status/expirationTimestamp")
+        .and_then(|v| v.as_str())
+        .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok());
+    match expiration {
+        Some(expires) if expires < chrono::Utc::now() => ExecOutcome::Failed(
+            FailureKind::ExpiredToken,
+            format!("'{}' returned a credential that expired at {}", command, expires.to_rfc3339()),
+        ),
+        Some(expires) => ExecOutcome::Ok(format!("{} (expires {})", command, expires.to_rfc3339())),
+        None => ExecOutcome::Ok(command.clone()),
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use futures::future::join_all;
+use k8s_openapi::apiextensions_apiserver::pkg::apis::apiextensions::v1::CustomResourceDefinition;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::APIResourceList;
+use kube::api::ListParams;
+use kube::discovery::ApiResource;
+use kube::{Api, Client};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, Result};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerVersion {
+    pub major: String,
+    pub minor: String,
+    pub git_version: String,
+    pub platform: String,
+    pub build_date: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiGroupInfo {
+    /// Empty for the core group
+    pub name: String,
+    pub versions: Vec<String>,
+    pub preferred_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiResourceInfo {
+    pub group: String,
+    pub version: String,
+    pub api_version: String,
+    pub kind: String,
+    pub plural: String,
+    pub singular: String,
+    pub short_names: Vec<String>,
+    pub namespaced: bool,
+    pub verbs: Vec<String>,
+    pub subresources: Vec<String>,
+    /// Served from a CustomResourceDefinition
+    pub custom: bool,
+}
+
+impl ApiResourceInfo {
+    /// Type information for `Api<DynamicObject>`
+    pub fn api_resource(&self) -> ApiResource {
+        ApiResource {
+            group: self.group.clone(),
+            version: self.version.clone(),
+            api_version: self.api_version.clone(),
+            kind: self.kind.clone(),
+            plural: self.plural.clone(),
+        }
+    }
+
+    pub fn supports(&self, verb: &str) -> bool {
+        self.verbs.iter().any(|v| v == verb)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterApiInfo {
+    pub server_version: ServerVersion,
+    pub groups: Vec<ApiGroupInfo>,
+    pub resources: Vec<ApiResourceInfo>,
+    /// Group versions whose discovery failed (e.g. an aggregated API that is down)
+    pub failed_group_versions: Vec<String>,
+    pub discovered_at: String,
+}
+
+impl ClusterApiInfo {
+    /// Resolve a kind, plural, singular or short name (case-insensitive) to a served
+    /// resource, in the group's preferred version. Without `api_version`, a name
+    /// served by more than one group (e.g. `Event` in both `v1` and
+    /// `events.k8s.io/v1`) is rejected rather than guessed.
+    pub fn find_resource(&self, api_version: Option<&str>, kind: &str) -> Result<&ApiResourceInfo> {
+        let matches_name = |r: &&ApiResourceInfo| {
+            r.kind.eq_ignore_ascii_case(kind)
+                || r.plural.eq_ignore_ascii_case(kind)
+                || r.singular.eq_ignore_ascii_case(kind)
+                || r.short_names.iter().any(|s| s.eq_ignore_ascii_case(kind))
+        };
+        let is_preferred = |r: &ApiResourceInfo| {
+            self.groups
+                .iter()
+                .any(|g| g.name == r.group && g.preferred_version.as_deref() == Some(r.version.as_str()))
+        };
+
+        let candidates: Vec<&ApiResourceInfo> = self
+            .resources
+            .iter()
+            .filter(matches_name)
+            .filter(|r| api_version.is_none_or(|v| r.api_version == v))
+            .collect();
+        let groups: BTreeSet<&str> = candidates.iter().map(|r| r.group.as_str()).collect();
+        if groups.len() > 1 {
+            let api_versions: BTreeSet<&str> = candidates.iter().map(|r| r.api_version.as_str()).collect();
+            return Err(AppError::Invalid(format!(
+                "Resource type '{}' is served by several API groups ({}): specify the apiVersion",
+                kind,
+                api_versions.into_iter().collect::<Vec<_>>().join(", ")
+            )));
+        }
+
+        candidates
+            .into_iter()
+            .min_by_key(|r| !is_preferred(r))
+            .ok_or_else(|| {
+                AppError::NotFound(match api_version {
+                    Some(v) => format!("Resource type '{}' in {} is not served by the cluster", kind, v),
+                    None => format!("Resource type '{}' is not served by the cluster", kind),
+                })
+            })
+    }
+}
+
+/// Fetch the server version and discover every served group, version and resource.
+///
+/// Unlike `kube::Discovery::run`, a group version that fails to answer (typically an
+/// unavailable aggregated API such as metrics.k8s.io) is recorded instead of failing
+/// the whole discovery.
+pub async fn discover(client: &Client) -> Result<ClusterApiInfo> {
+    let version = client.apiserver_version().await?;
+    let server_version = ServerVersion {
+        major: version.major,
+        minor: version.minor,
+        git_version: version.git_version,
+        platform: version.platform,
+        build_date: version.build_date,
+    };
+
+    let mut groups = Vec::new();
+    // (group, version) pairs to query
+    let mut targets: Vec<(String, String)> = Vec::new();
+
+    let core = client.list_core_api_versions().await?;
+    for version in &core.versions {
+        targets.push((String::new(), version.clone()));
+    }
+    groups.push(ApiGroupInfo {
+        name: String::new(),
+        versions: core.versions.clone(),
+        preferred_version: core.versions.first().cloned(),
+    });
+
+    let api_groups = client.list_api_groups().await?;
+    for group in api_groups.groups {
+        let versions: Vec<String> = group.versions.iter().map(|v| v.version.clone()).collect();
+        for version in &versions {
+            targets.push((group.name.clone(), version.clone()));
+        }
+        groups.push(ApiGroupInfo {
+            name: group.name,
+            versions,
+            preferred_version: group.preferred_version.map(|v| v.version),
+        });
+    }
+
+    let lists = join_all(targets.iter().map(|(group, version)| async move {
+        if group.is_empty() {
+            client.list_core_api_resources(version).await
+        } else {
+            client
+                .list_api_group_resources(&format!("{}/{}", group, version))
+                .await
+        }
+    }))
+    .await;
+
+    let custom = list_custom_resources(client).await;
+
+    let mut resources = Vec::new();
+    let mut failed_group_versions = Vec::new();
+    for ((group, version), list) in targets.iter().zip(lists) {
+        match list {
+            Ok(list) => resources.extend(resources_from_list(group, version, list, &custom)),
+            Err(e) => {
+                let group_version = if group.is_empty() {
+                    version.clone()
+                } else {
+                    format!("{}/{}", group, version)
+                };
+                tracing::warn!("Discovery failed for {}: {}", group_version, e);
+                failed_group_versions.push(group_version);
+            }
+        }
+    }
+
+    Ok(ClusterApiInfo {
+        server_version,
+        groups,
+        resources,
+        failed_group_versions,
+        discovered_at: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+/// `(group, plural)` of every CRD. Empty if CRDs can't be listed.
+async fn list_custom_resources(client: &Client) -> HashSet<(String, String)> {
+    let crds: Api<CustomResourceDefinition> = Api::all(client.clone());
+    match crds.list_metadata(&ListParams::default()).await {
+        Ok(list) => list
+            .items
+            .into_iter()
+            .filter_map(|crd| crd.metadata.name)
+            // CRD names are always `<plural>.<group>`
+            .filter_map(|name| {
+                name.split_once('.')
+                    .map(|(plural, group)| (group.to_string(), plural.to_string()))
+            })
+            .collect(),
+        Err(e) => {
+            tracing::warn!("Failed to list CustomResourceDefinitions: {}", e);
+            HashSet::new()
+        }
+    }
+}
+
+fn resources_from_list(
+    group: &str,
+    version: &str,
+    list: APIResourceList,
+    custom: &HashSet<(String, String)>,
+) -> Vec<ApiResourceInfo> {
+    let api_version = list.group_version;
+
+    // Subresources are listed as `<resource>/<subresource>`
+    let mut subresources: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for resource in &list.resources {
+        if let Some((parent, sub)) = resource.name.split_once('/') {
+            subresources
+                .entry(parent.to_string())
+                .or_default()
+                .push(sub.to_string());
+        }
+    }
+
+    list.resources
+        .into_iter()
+        .filter(|r| !r.name.contains('/'))
+        .map(|r| ApiResourceInfo {
+            group: group.to_string(),
+            version: version.to_string(),
+            api_version: api_version.clone(),
+            custom: custom.contains(&(group.to_string(), r.name.clone())),
+            subresources: subresources.remove(&r.name).unwrap_or_default(),
+            singular: if r.singular_name.is_empty() {
+                r.kind.to_lowercase()
+            } else {
+                r.singular_name
+            },
+            kind: r.kind,
+            plural: r.name,
+            short_names: r.short_names.unwrap_or_default(),
+            namespaced: r.namespaced,
+            verbs: r.verbs,
+        })
+        .collect()
+}
@@ -0,0 +1,253 @@
+use std::time::Duration;
+
+use k8s_openapi::api::core::v1::Pod;
+use kube::api::{Api, DeleteParams, EvictParams, ListParams};
+use kube::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, Result};
+use crate::kubernetes;
+
+const MIRROR_POD_ANNOTATION: &str = "kubernetes.io/config.mirror";
+/// First wait before retrying an eviction blocked by a PodDisruptionBudget
+const EVICTION_RETRY_INITIAL: Duration = Duration::from_secs(2);
+const EVICTION_RETRY_MAX: Duration = Duration::from_secs(30);
+const DELETION_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DrainOptions {
+    /// Evict pods using emptyDir volumes; their data is lost
+    pub delete_emptydir_data: bool,
+    /// Evict pods that no controller will recreate
+    pub force: bool,
+    /// Overrides each pod's own grace period
+    pub grace_period_seconds: Option<u32>,
+    /// Give up after this long (default 5 minutes); the node stays cordoned
+    pub timeout_seconds: Option<u64>,
+}
+
+impl DrainOptions {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_seconds.unwrap_or(300))
+    }
+
+    /// Short description for the audit log
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if self.delete_emptydir_data {
+            parts.push("delete-emptydir-data".to_string());
+        }
+        if self.force {
+            parts.push("force".to_string());
+        }
+        if let Some(grace) = self.grace_period_seconds {
+            parts.push(format!("grace={}s", grace));
+        }
+        parts.push(format!("timeout={}s", self.timeout().as_secs()));
+        parts.join(" ")
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DrainPodPhase {
+    Pending,
+    /// Left on the node: DaemonSet and mirror pods
+    Skipped,
+    /// Eviction refused by a PodDisruptionBudget, retrying
+    Blocked,
+    /// Eviction accepted, waiting for the pod to terminate
+    Evicting,
+    Evicted,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrainPod {
+    pub namespace: String,
+    pub name: String,
+    pub phase: DrainPodPhase,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DrainStatus {
+    Running,
+    Complete,
+    Failed,
+    TimedOut,
+    Cancelled,
+}
+
+/// Emitted as `node-drain-<id>` while a drain runs. The final event (status other
+/// than `running`) carries every pod's last state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrainProgress {
+    pub node: String,
+    pub status: DrainStatus,
+    /// The pod this update is about; `None` on the final event
+    pub pod: Option<DrainPod>,
+    pub pods: Vec<DrainPod>,
+    pub message: Option<String>,
+    pub timestamp: String,
+}
+
+/// A drain that passed its checks and whose node is cordoned
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrainPlan {
+    pub node: String,
+    pub evict: Vec<DrainPod>,
+    pub skipped: Vec<DrainPod>,
+    #[serde(skip)]
+    pub(crate) pods: Vec<Pod>,
+}
+
+/// Returned by `drain_node`. While `status` is `running`, follow
+/// `node-drain-<drain_id>` for progress (after calling `watch_listener_ready`)
+/// and stop the drain with `stop_watch`. A node with nothing to evict is
+/// `complete` right away and has no `drain_id`.
+#[derive(Debug, Clone, Serialize)]
+pub struct DrainStarted {
+    pub drain_id: Option<String>,
+    pub status: DrainStatus,
+    pub plan: DrainPlan,
+}
+
+/// Check the node's pods, then cordon it. Fails without cordoning if pods would
+/// be lost without `force` / `delete_emptydir_data`, like `kubectl drain`.
+pub async fn prepare(client: &Client, node: &str, options: &DrainOptions) -> Result<DrainPlan> {
+    let pods: Api<Pod> = Api::all(client.clone());
+    let on_node = pods
+        .list(&ListParams::default().fields(&format!("spec.nodeName={}", node)))
+        .await?;
+
+    let mut plan = DrainPlan {
+        node: node.to_string(),
+        evict: Vec::new(),
+        skipped: Vec::new(),
+        pods: Vec::new(),
+    };
+    let mut blockers = Vec::new();
+    for pod in on_node.items {
+        let entry = |phase, message: Option<&str>| DrainPod {
+            namespace: pod.metadata.namespace.clone().unwrap_or_default(),
+            name: pod.metadata.name.clone().unwrap_or_default(),
+            phase,
+            message: message.map(str::to_string),
+        };
+        let finished = matches!(
+            pod.status.as_ref().and_then(|s| s.phase.as_deref()),
+            Some("Succeeded") | Some("Failed")
+        );
+        let controller_kind = pod
+            .metadata
+            .owner_references
+            .as_ref()
+            .and_then(|refs| refs.iter().find(|r| r.controller == Some(true)))
+            .map(|r| r.kind.as_str());
+
+        if pod.metadata.annotations.as_ref().is_some_and(|a| a.contains_key(MIRROR_POD_ANNOTATION)) {
+            plan.skipped.push(entry(DrainPodPhase::Skipped, Some("mirror pod")));
+            continue;
+        }
+        if controller_kind == Some("DaemonSet") {
+            plan.skipped.push(entry(DrainPodPhase::Skipped, Some("DaemonSet pod")));
+            continue;
+        }
+        if !finished {
+            let pod_ref = format!(
+                "{}/{}",
+                pod.metadata.namespace.as_deref().unwrap_or_default(),
+                pod.metadata.name.as_deref().unwrap_or_default()
+            );
+            if controller_kind.is_none() && !options.force {
+                blockers.push(format!("{} is not managed by a controller (use force)", pod_ref));
+            }
+            let uses_empty_dir = pod
+                .spec
+                .as_ref()
+                .and_then(|s| s.volumes.as_ref())
+                .is_some_and(|v| v.iter().any(|v| v.empty_dir.is_some()));
+            if uses_empty_dir && !options.delete_emptydir_data {
+                blockers.push(format!("{} uses emptyDir volumes (allow deleting emptyDir data)", pod_ref));
+            }
+        }
+        plan.evict.push(entry(DrainPodPhase::Pending, None));
+        plan.pods.push(pod);
+    }
+
+    if !blockers.is_empty() {
+        return Err(AppError::Invalid(format!("Cannot drain node {}: {}", node, blockers.join("; "))));
+    }
+
+    kubernetes::cordon_node(client, node).await?;
+    Ok(plan)
+}
+
+/// Evict one pod, retrying while a PodDisruptionBudget blocks it, then wait for
+/// it to go away. `report` is called on every state change.
+pub async fn evict_pod(client: &Client, pod: &Pod, grace_period_seconds: Option<u32>, report: impl Fn(DrainPod)) {
+    let namespace = pod.metadata.namespace.clone().unwrap_or_default();
+    let name = pod.metadata.name.clone().unwrap_or_default();
+    let update = |phase, message: Option<String>| {
+        report(DrainPod {
+            namespace: namespace.clone(),
+            name: name.clone(),
+            phase,
+            message,
+        })
+    };
+
+    let api: Api<Pod> = Api::namespaced(client.clone(), &namespace);
+    let params = EvictParams {
+        delete_options: Some(DeleteParams {
+            grace_period_seconds,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let mut backoff = EVICTION_RETRY_INITIAL;
+    loop {
+        match api.evict(&name, &params).await {
+            Ok(_) => break,
+            Err(kube::Error::Api(response)) if response.code == 404 => {
+                update(DrainPodPhase::Evicted, None);
+                return;
+            }
+            Err(kube::Error::Api(response)) if response.code == 429 => {
+                update(
+                    DrainPodPhase::Blocked,
+                    Some(format!("{} (retrying in {}s)", response.message, backoff.as_secs())),
+                );
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(EVICTION_RETRY_MAX);
+            }
+            Err(e) => {
+                update(DrainPodPhase::Failed, Some(AppError::from(e).to_string()));
+                return;
+            }
+        }
+    }
+
+    update(DrainPodPhase::Evicting, None);
+    let uid = pod.metadata.uid.clone();
+    loop {
+        match api.get_opt(&name).await {
+            // A pod with the same name but a new uid is a replacement (e.g. StatefulSet)
+            Ok(Some(current)) if current.metadata.uid == uid => {
+                tokio::time::sleep(DELETION_POLL_INTERVAL).await;
+            }
+            Ok(_) => {
+                update(DrainPodPhase::Evicted, None);
+                return;
+            }
+            Err(e) => {
+                update(DrainPodPhase::Failed, Some(AppError::from(e).to_string()));
+                return;
+            }
+        }
+    }
+}
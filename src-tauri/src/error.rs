@@ -1,3 +1,5 @@
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{StatusCause, StatusDetails};
+use kube::error::ErrorResponse;
 use serde::Serialize;
 use thiserror::Error;
 
@@ -21,17 +23,171 @@ pub enum AppError {
     #[error("YAML serialization error: {0}")]
     YamlSerialization(#[from] serde_yaml::Error),
 
+    #[error("{0}")]
+    NotFound(String),
+
+    #[error("{0}")]
+    Conflict(String),
+
+    #[error("{0}")]
+    Invalid(String),
+
+    #[error("Context '{0}' is protected: type the context name to confirm")]
+    ConfirmationRequired(String),
+
+    #[error("{0}")]
+    ReadOnly(String),
+
     #[error("{0}")]
     Custom(String),
 }
 
+impl AppError {
+    /// Stable, machine-readable error code for the frontend
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Kube(kube::Error::Api(response)) => match (response.code, response.reason.as_str()) {
+                (400, _) => "bad_request",
+                (401, _) => "unauthorized",
+                (403, _) => "forbidden",
+                (404, _) => "not_found",
+                (409, "AlreadyExists") => "already_exists",
+                (409, _) => "conflict",
+                (410, _) => "gone",
+                (422, _) => "invalid",
+                (429, _) => "too_many_requests",
+                (504, _) => "timeout",
+                (500..=599, _) => "server_error",
+                _ => "api_error",
+            },
+            AppError::Kube(kube::Error::HyperError(_)) | AppError::Kube(kube::Error::Service(_)) => "unreachable",
+            AppError::Kube(kube::Error::Auth(_)) => "auth_failed",
+            AppError::Kube(_) => "kube_error",
+            AppError::KubeConfig(_) | AppError::InferConfig(_) => "kubeconfig",
+            AppError::Io(_) => "io",
+            AppError::Serialization(_) | AppError::YamlSerialization(_) => "serialization",
+            AppError::NotFound(_) => "not_found",
+            AppError::Conflict(_) => "conflict",
+            AppError::Invalid(_) => "invalid",
+            AppError::ConfirmationRequired(_) => "confirmation_required",
+            AppError::ReadOnly(_) => "read_only",
+            AppError::Custom(_) => "error",
+        }
+    }
+
+    /// HTTP status returned by the API server, if the error came from one
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            AppError::Kube(kube::Error::Api(response)) => Some(response.code),
+            _ => None,
+        }
+    }
+}
+
+/// Errors reach the frontend as `{ code, message, status, reason, details }`.
+/// `status`, `reason` and `details` come from the Kubernetes `Status` for API errors.
+#[derive(Serialize)]
+struct ErrorPayload<'a> {
+    code: &'static str,
+    message: String,
+    status: Option<u16>,
+    reason: Option<&'a str>,
+    details: Option<StatusDetails>,
+}
+
 impl Serialize for AppError {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        let (message, reason, details) = match self {
+            AppError::Kube(kube::Error::Api(response)) => (
+                response.message.clone(),
+                Some(response.reason.as_str()).filter(|r| !r.is_empty()),
+                status_details(response),
+            ),
+            _ => (self.to_string(), None, None),
+        };
+
+        ErrorPayload {
+            code: self.code(),
+            message,
+            status: self.status(),
+            reason,
+            details,
+        }
+        .serialize(serializer)
     }
 }
 
+/// Field error prefixes the API server uses, with their `StatusCause` reasons
+const CAUSE_TYPES: &[(&str, &str)] = &[
+    ("Invalid value", "FieldValueInvalid"),
+    ("Required value", "FieldValueRequired"),
+    ("Unsupported value", "FieldValueNotSupported"),
+    ("Duplicate value", "FieldValueDuplicate"),
+    ("Not found", "FieldValueNotFound"),
+    ("Forbidden", "FieldValueForbidden"),
+    ("Too long", "FieldValueTooLong"),
+    ("Must have at most", "FieldValueTooMany"),
+    ("Internal error", "InternalError"),
+];
+
+/// The `details` of an API error's `Status`. kube's `ErrorResponse` drops them,
+/// so they are rebuilt from the message, which the API server formats from the
+/// same details: `<Kind.group> "<name>" is invalid: <field errors>` for
+/// validation errors and `<resource.group> "<name>" ...` for the rest.
+fn status_details(response: &ErrorResponse) -> Option<StatusDetails> {
+    let subject_regex = regex::Regex::new(
+        r#"^(?:Operation cannot be fulfilled on )?([A-Za-z0-9][A-Za-z0-9.-]*)(?: "([^"]*)")?(?: is invalid: (.*)| (?:not found|already exists|is forbidden)|:)"#,
+    )
+    .unwrap();
+    let captures = subject_regex.captures(&response.message)?;
+
+    let (kind, group) = match captures[1].split_once('.') {
+        Some((kind, group)) => (kind.to_string(), Some(group.to_string())),
+        None => (captures[1].to_string(), None),
+    };
+    let causes = captures.get(3).map(|errors| field_causes(errors.as_str()));
+
+    Some(StatusDetails {
+        name: captures.get(2).map(|name| name.as_str().to_string()),
+        group,
+        kind: Some(kind),
+        causes,
+        ..Default::default()
+    })
+}
+
+/// Split an aggregated field error list such as
+/// `[spec.replicas: Invalid value: -1: must be ..., spec.selector: Required value]`
+fn field_causes(errors: &str) -> Vec<StatusCause> {
+    let errors = errors
+        .strip_prefix('[')
+        .and_then(|e| e.strip_suffix(']'))
+        .unwrap_or(errors);
+    let prefixes = CAUSE_TYPES.iter().map(|(prefix, _)| *prefix).collect::<Vec<_>>().join("|");
+    let cause_regex = regex::Regex::new(&format!(r"(?:^|, )([^\s:,]+): ({})", prefixes)).unwrap();
+
+    let starts: Vec<_> = cause_regex.captures_iter(errors).collect();
+    starts
+        .iter()
+        .enumerate()
+        .map(|(i, captures)| {
+            let field = &captures[1];
+            let body_start = captures.get(2).unwrap().start();
+            let body_end = starts.get(i + 1).map(|next| next.get(0).unwrap().start()).unwrap_or(errors.len());
+            let reason = CAUSE_TYPES
+                .iter()
+                .find(|(prefix, _)| *prefix == &captures[2])
+                .map(|(_, reason)| reason.to_string());
+            StatusCause {
+                field: Some(field.to_string()),
+                message: Some(errors[body_start..body_end].to_string()),
+                reason,
+            }
+        })
+        .collect()
+}
+
 pub type Result<T> = std::result::Result<T, AppError>;
@@ -0,0 +1,396 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+
+use crate::error::{AppError, Result};
+use crate::kubernetes::{get_kubeconfig_paths, read_kubeconfig, read_kubeconfig_document};
+
+/// Marker placed between the kubeconfig file name and the backup timestamp
+const BACKUP_MARKER: &str = ".apex-backup-";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KubeconfigBackup {
+    pub path: String,
+    pub original_path: String,
+    pub created_at: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KubeconfigImportResult {
+    pub target_path: String,
+    pub backup_path: Option<String>,
+    pub added_contexts: Vec<String>,
+    pub added_clusters: Vec<String>,
+    pub added_users: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextDeleteResult {
+    pub path: String,
+    pub backup_path: Option<String>,
+    pub removed_clusters: Vec<String>,
+    pub removed_users: Vec<String>,
+}
+
+// ============ Backups ============
+
+/// Copy a kubeconfig file to `<file>.apex-backup-<timestamp>` next to it
+pub fn backup_kubeconfig_file(path: &Path) -> Result<Option<PathBuf>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| AppError::Custom(format!("Invalid kubeconfig path: {}", path.display())))?;
+    let timestamp = chrono::Utc::now().format("%Y%m%d-%H%M%S%.3f");
+    let backup_path = path.with_file_name(format!("{}{}{}", file_name, BACKUP_MARKER, timestamp));
+
+    std::fs::copy(path, &backup_path)
+        .map_err(|e| AppError::Custom(format!("Failed to back up {}: {}", path.display(), e)))?;
+    tracing::info!("Backed up kubeconfig {:?} to {:?}", path, backup_path);
+    Ok(Some(backup_path))
+}
+
+/// Back up the file, then write the document over it
+pub fn write_kubeconfig_document(path: &Path, doc: &Value) -> Result<Option<PathBuf>> {
+    let yaml = serde_yaml::to_string(doc)
+        .map_err(|e| AppError::Custom(format!("Failed to serialize config: {}", e)))?;
+    let backup_path = backup_kubeconfig_file(path)?;
+    std::fs::write(path, yaml)?;
+    Ok(backup_path)
+}
+
+pub fn list_kubeconfig_backups() -> Result<Vec<KubeconfigBackup>> {
+    let mut backups = Vec::new();
+
+    for original in get_kubeconfig_paths() {
+        let (Some(dir), Some(file_name)) = (original.parent(), original.file_name()) else {
+            continue;
+        };
+        let prefix = format!("{}{}", file_name.to_string_lossy(), BACKUP_MARKER);
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.starts_with(&prefix) {
+                continue;
+            }
+            let metadata = match entry.metadata() {
+                Ok(m) if m.is_file() => m,
+                _ => continue,
+            };
+            let created_at = metadata
+                .modified()
+                .map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339())
+                .unwrap_or_default();
+
+            backups.push(KubeconfigBackup {
+                path: entry.path().display().to_string(),
+                original_path: original.display().to_string(),
+                created_at,
+                size: metadata.len(),
+            });
+        }
+    }
+
+    // Newest first
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(backups)
+}
+
+/// Restore a backup over its original file. The current file is backed up first,
+/// so a restore can itself be undone.
+pub fn restore_kubeconfig_backup(backup_path: &str) -> Result<KubeconfigBackup> {
+    // Only restore files we created, never arbitrary paths
+    let backup = list_kubeconfig_backups()?
+        .into_iter()
+        .find(|b| b.path == backup_path)
+        .ok_or_else(|| AppError::NotFound(format!("Backup '{}' not found", backup_path)))?;
+
+    // Make sure the backup is still a readable kubeconfig before overwriting anything
+    kube::config::Kubeconfig::read_from(&backup.path)?;
+
+    let original = PathBuf::from(&backup.original_path);
+    backup_kubeconfig_file(&original)?;
+    std::fs::copy(&backup.path, &original)
+        .map_err(|e| AppError::Custom(format!("Failed to restore {}: {}", backup.path, e)))?;
+
+    Ok(backup)
+}
+
+// ============ Document helpers ============
+
+fn as_mapping_mut<'a>(doc: &'a mut Value, path: &Path) -> Result<&'a mut Mapping> {
+    doc.as_mapping_mut()
+        .ok_or_else(|| AppError::Custom(format!("Invalid kubeconfig: {}", path.display())))
+}
+
+/// Get (creating if missing) one of the named lists: `clusters`, `users` or `contexts`
+fn named_list_mut<'a>(doc: &'a mut Value, key: &str, path: &Path) -> Result<&'a mut Vec<Value>> {
+    let mapping = as_mapping_mut(doc, path)?;
+    let entry = mapping
+        .entry(Value::String(key.to_string()))
+        .or_insert_with(|| Value::Sequence(Vec::new()));
+    if entry.is_null() {
+        *entry = Value::Sequence(Vec::new());
+    }
+    entry
+        .as_sequence_mut()
+        .ok_or_else(|| AppError::Custom(format!("Invalid '{}' list in {}", key, path.display())))
+}
+
+fn entry_name(entry: &Value) -> Option<&str> {
+    entry.get("name").and_then(|n| n.as_str())
+}
+
+fn named_list_contains(doc: &Value, key: &str, name: &str) -> bool {
+    doc.get(key)
+        .and_then(|v| v.as_sequence())
+        .map(|items| items.iter().any(|e| entry_name(e) == Some(name)))
+        .unwrap_or(false)
+}
+
+/// Find the file that defines a context. With several kubeconfig files the first
+/// definition wins, so that's the one to edit.
+fn find_context_file(context_name: &str) -> Result<(PathBuf, Value)> {
+    for path in get_kubeconfig_paths() {
+        if !path.exists() {
+            continue;
+        }
+        let doc = read_kubeconfig_document(&path)?;
+        if named_list_contains(&doc, "contexts", context_name) {
+            return Ok((path, doc));
+        }
+    }
+    Err(AppError::NotFound(format!(
+        "Context '{}' not found",
+        context_name
+    )))
+}
+
+fn find_context_mut<'a>(doc: &'a mut Value, context_name: &str, path: &Path) -> Result<&'a mut Value> {
+    named_list_mut(doc, "contexts", path)?
+        .iter_mut()
+        .find(|e| entry_name(e) == Some(context_name))
+        .ok_or_else(|| AppError::NotFound(format!(
+            "Context '{}' not found",
+            context_name
+        )))
+}
+
+fn validate_context_name(name: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        return Err(AppError::Invalid("Context name is required".to_string()));
+    }
+    if name.trim() != name {
+        return Err(AppError::Invalid("Context name cannot start or end with whitespace".to_string()));
+    }
+    Ok(())
+}
+
+fn validate_namespace_name(namespace: &str) -> Result<()> {
+    if namespace.len() > 63 {
+        return Err(AppError::Invalid("Namespace must be 63 characters or less".to_string()));
+    }
+    let namespace_regex = regex::Regex::new(r"^[a-z0-9]([-a-z0-9]*[a-z0-9])?$").unwrap();
+    if !namespace_regex.is_match(namespace) {
+        return Err(AppError::Invalid(
+            "Namespace must be lowercase alphanumeric or '-', and start/end with alphanumeric".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+// ============ Editing ============
+
+/// Merge the clusters, users and contexts of another kubeconfig file into the
+/// primary kubeconfig. Entries whose name already exists are skipped rather than
+/// overwritten.
+pub fn import_kubeconfig(source_path: &str) -> Result<KubeconfigImportResult> {
+    // read_from resolves relative certificate/key paths against the source file,
+    // so the imported entries keep working from their new location
+    let source = kube::config::Kubeconfig::read_from(source_path)?;
+    let existing = read_kubeconfig()?;
+
+    let target = get_kubeconfig_paths()
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::NotFound("No kubeconfig file found".to_string()))?;
+    let mut doc = if target.exists() {
+        read_kubeconfig_document(&target)?
+    } else {
+        let mut mapping = Mapping::new();
+        mapping.insert(Value::String("apiVersion".to_string()), Value::String("v1".to_string()));
+        mapping.insert(Value::String("kind".to_string()), Value::String("Config".to_string()));
+        Value::Mapping(mapping)
+    };
+
+    let mut skipped = Vec::new();
+
+    let mut added_clusters = Vec::new();
+    for cluster in &source.clusters {
+        if existing.clusters.iter().any(|c| c.name == cluster.name) {
+            skipped.push(format!("cluster/{}", cluster.name));
+            continue;
+        }
+        named_list_mut(&mut doc, "clusters", &target)?.push(serde_yaml::to_value(cluster)?);
+        added_clusters.push(cluster.name.clone());
+    }
+
+    let mut added_users = Vec::new();
+    for user in &source.auth_infos {
+        if existing.auth_infos.iter().any(|u| u.name == user.name) {
+            skipped.push(format!("user/{}", user.name));
+            continue;
+        }
+        named_list_mut(&mut doc, "users", &target)?.push(serde_yaml::to_value(user)?);
+        added_users.push(user.name.clone());
+    }
+
+    let mut added_contexts = Vec::new();
+    for context in &source.contexts {
+        if existing.contexts.iter().any(|c| c.name == context.name) {
+            skipped.push(format!("context/{}", context.name));
+            continue;
+        }
+        named_list_mut(&mut doc, "contexts", &target)?.push(serde_yaml::to_value(context)?);
+        added_contexts.push(context.name.clone());
+    }
+
+    let backup_path = if added_clusters.is_empty() && added_users.is_empty() && added_contexts.is_empty() {
+        None
+    } else {
+        write_kubeconfig_document(&target, &doc)?
+    };
+
+    Ok(KubeconfigImportResult {
+        target_path: target.display().to_string(),
+        backup_path: backup_path.map(|p| p.display().to_string()),
+        added_contexts,
+        added_clusters,
+        added_users,
+        skipped,
+    })
+}
+
+pub fn rename_context(old_name: &str, new_name: &str) -> Result<()> {
+    validate_context_name(new_name)?;
+    if old_name == new_name {
+        return Ok(());
+    }
+
+    let merged = read_kubeconfig()?;
+    if merged.contexts.iter().any(|c| c.name == new_name) {
+        return Err(AppError::Conflict(format!(
+            "Context '{}' already exists",
+            new_name
+        )));
+    }
+
+    let (path, mut doc) = find_context_file(old_name)?;
+    let context = find_context_mut(&mut doc, old_name, &path)?;
+    if let Some(mapping) = context.as_mapping_mut() {
+        mapping.insert(Value::String("name".to_string()), Value::String(new_name.to_string()));
+    }
+
+    let mapping = as_mapping_mut(&mut doc, &path)?;
+    let current_key = Value::String("current-context".to_string());
+    if mapping.get(&current_key).and_then(|v| v.as_str()) == Some(old_name) {
+        mapping.insert(current_key, Value::String(new_name.to_string()));
+    }
+
+    write_kubeconfig_document(&path, &doc)?;
+    Ok(())
+}
+
+/// Delete a context, then remove its cluster and user from the same file if no
+/// remaining context (in any kubeconfig file) still references them.
+pub fn delete_context(context_name: &str) -> Result<ContextDeleteResult> {
+    let merged = read_kubeconfig()?;
+    let deleted = merged
+        .contexts
+        .iter()
+        .find(|c| c.name == context_name)
+        .and_then(|c| c.context.clone());
+    let remaining: Vec<_> = merged
+        .contexts
+        .iter()
+        .filter(|c| c.name != context_name)
+        .filter_map(|c| c.context.as_ref())
+        .collect();
+
+    let (path, mut doc) = find_context_file(context_name)?;
+    named_list_mut(&mut doc, "contexts", &path)?.retain(|e| entry_name(e) != Some(context_name));
+
+    let mapping = as_mapping_mut(&mut doc, &path)?;
+    let current_key = Value::String("current-context".to_string());
+    if mapping.get(&current_key).and_then(|v| v.as_str()) == Some(context_name) {
+        mapping.remove(&current_key);
+    }
+
+    let mut removed_clusters = Vec::new();
+    let mut removed_users = Vec::new();
+
+    if let Some(ctx) = deleted {
+        if !remaining.iter().any(|c| c.cluster == ctx.cluster)
+            && named_list_contains(&doc, "clusters", &ctx.cluster)
+        {
+            named_list_mut(&mut doc, "clusters", &path)?.retain(|e| entry_name(e) != Some(ctx.cluster.as_str()));
+            removed_clusters.push(ctx.cluster.clone());
+        }
+        if let Some(user) = ctx.user {
+            if !remaining.iter().any(|c| c.user.as_ref() == Some(&user))
+                && named_list_contains(&doc, "users", &user)
+            {
+                named_list_mut(&mut doc, "users", &path)?.retain(|e| entry_name(e) != Some(user.as_str()));
+                removed_users.push(user);
+            }
+        }
+    }
+
+    let backup_path = write_kubeconfig_document(&path, &doc)?;
+
+    Ok(ContextDeleteResult {
+        path: path.display().to_string(),
+        backup_path: backup_path.map(|p| p.display().to_string()),
+        removed_clusters,
+        removed_users,
+    })
+}
+
+/// Set (or clear, with `None`/empty) the default namespace of a context
+pub fn set_context_namespace(context_name: &str, namespace: Option<&str>) -> Result<()> {
+    let namespace = namespace.map(str::trim).filter(|ns| !ns.is_empty());
+    if let Some(ns) = namespace {
+        validate_namespace_name(ns)?;
+    }
+
+    let (path, mut doc) = find_context_file(context_name)?;
+    let entry = find_context_mut(&mut doc, context_name, &path)?
+        .as_mapping_mut()
+        .ok_or_else(|| AppError::Custom(format!("Invalid context '{}'", context_name)))?;
+    let context = entry
+        .entry(Value::String("context".to_string()))
+        .or_insert_with(|| Value::Mapping(Mapping::new()))
+        .as_mapping_mut()
+        .ok_or_else(|| AppError::Custom(format!("Invalid context '{}'", context_name)))?;
+
+    let namespace_key = Value::String("namespace".to_string());
+    match namespace {
+        Some(ns) => {
+            context.insert(namespace_key, Value::String(ns.to_string()));
+        }
+        None => {
+            context.remove(&namespace_key);
+        }
+    }
+
+    write_kubeconfig_document(&path, &doc)?;
+    Ok(())
+}
@@ -1,10 +1,10 @@
 use kube::{
-    api::{Api, DeleteParams, ListParams, LogParams, Patch, PatchParams},
+    api::{Api, DeleteParams, ListParams, LogParams, Patch, PatchParams, PostParams},
     Client, Config,
 };
 use k8s_openapi::api::core::v1::{
-    Namespace, Node, PersistentVolume, PersistentVolumeClaim, Pod, Secret,
-    Service, ServiceAccount, ConfigMap,
+    Namespace, Node, PersistentVolume, PersistentVolumeClaim, Pod, ReplicationController,
+    Secret, Service, ServiceAccount, ConfigMap,
 };
 use k8s_openapi::api::apps::v1::{DaemonSet, Deployment, ReplicaSet, StatefulSet};
 use k8s_openapi::api::batch::v1::{CronJob, Job};
@@ -69,11 +69,14 @@ pub struct DeploymentDetail {
     pub updated_replicas: i32,
     pub available_replicas: i32,
     pub strategy: String,
+    pub paused: bool,
     pub min_ready_seconds: i32,
     pub revision_history_limit: Option<i32>,
     pub selector: std::collections::BTreeMap<String, String>,
     pub conditions: Vec<DeploymentCondition>,
     pub container_images: Vec<String>,
+    /// Container names, in the same order as `container_images`
+    pub container_names: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -122,10 +125,14 @@ pub struct StatefulSetDetail {
     pub service_name: String,
     pub pod_management_policy: String,
     pub update_strategy: String,
+    /// Pods with an ordinal below the partition keep the old revision
+    pub partition: Option<i32>,
     pub revision_history_limit: Option<i32>,
     pub selector: std::collections::BTreeMap<String, String>,
     pub conditions: Vec<StatefulSetCondition>,
     pub container_images: Vec<String>,
+    /// Container names, in the same order as `container_images`
+    pub container_names: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -498,16 +505,51 @@ pub struct PulseMetrics {
     pub metrics_available: bool,
 }
 
-fn get_kubeconfig_path() -> PathBuf {
+/// Resolve the kubeconfig files to load, in kubectl precedence order.
+/// `KUBECONFIG` may list several files (`:`-separated, `;` on Windows); like kubectl,
+/// empty entries, duplicates and files that don't exist are skipped.
+pub fn get_kubeconfig_paths() -> Vec<PathBuf> {
     // 1. Check KUBECONFIG env var first
-    if let Ok(path) = std::env::var("KUBECONFIG") {
-        let path = PathBuf::from(path);
-        if path.exists() {
-            return path;
+    if let Some(value) = std::env::var_os("KUBECONFIG") {
+        let mut paths: Vec<PathBuf> = Vec::new();
+        for path in std::env::split_paths(&value) {
+            if path.as_os_str().is_empty() || paths.contains(&path) {
+                continue;
+            }
+            if path.exists() {
+                paths.push(path);
+            }
+        }
+        if !paths.is_empty() {
+            return paths;
         }
     }
 
-    // 2. Check native path (works on macOS, Linux, and Windows native)
+    vec![get_default_kubeconfig_path()]
+}
+
+/// Modification times of the kubeconfig files, used to tell when cached clients
+/// were built from a kubeconfig that has since changed
+pub fn get_kubeconfig_mtimes() -> Vec<(PathBuf, Option<std::time::SystemTime>)> {
+    get_kubeconfig_paths()
+        .into_iter()
+        .map(|path| {
+            let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// The primary kubeconfig file (first in precedence order)
+fn get_kubeconfig_path() -> PathBuf {
+    get_kubeconfig_paths()
+        .into_iter()
+        .next()
+        .unwrap_or_else(get_default_kubeconfig_path)
+}
+
+fn get_default_kubeconfig_path() -> PathBuf {
+    // Check native path (works on macOS, Linux, and Windows native)
     let native_path = dirs::home_dir()
         .unwrap_or_default()
         .join(".kube")
@@ -517,7 +559,7 @@ fn get_kubeconfig_path() -> PathBuf {
         return native_path;
     }
 
-    // 3. On Windows, check WSL paths if native doesn't exist
+    // On Windows, check WSL paths if native doesn't exist
     #[cfg(target_os = "windows")]
     {
         if let Some(wsl_path) = find_wsl_kubeconfig() {
@@ -585,43 +627,44 @@ fn find_wsl_kubeconfig() -> Option<PathBuf> {
     None
 }
 
-// Public accessor for kubeconfig path (used by portforward module)
-pub fn get_kubeconfig_path_for_portforward() -> PathBuf {
-    get_kubeconfig_path()
+/// Read and merge every kubeconfig file following kubectl's rules:
+/// the first file to define a cluster, user, context or `current-context` wins.
+pub fn read_kubeconfig() -> Result<kube::config::Kubeconfig> {
+    let mut merged = kube::config::Kubeconfig::default();
+    for path in get_kubeconfig_paths() {
+        merged = merged.merge(kube::config::Kubeconfig::read_from(&path)?)?;
+    }
+    Ok(merged)
 }
 
 // Startup check functions
 pub async fn get_kubeconfig_path_string() -> Result<String> {
-    let path = get_kubeconfig_path();
-    if !path.exists() {
-        return Err(AppError::Custom(format!(
+    let paths = get_kubeconfig_paths();
+    if !paths.iter().any(|p| p.exists()) {
+        return Err(AppError::NotFound(format!(
             "Kubeconfig not found at: {}",
-            path.display()
+            get_kubeconfig_path().display()
         )));
     }
-    Ok(path.display().to_string())
+    Ok(paths
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", "))
 }
 
 pub async fn validate_kubeconfig() -> Result<()> {
-    let path = get_kubeconfig_path();
-    let _kubeconfig = kube::config::Kubeconfig::read_from(&path)?;
+    let _kubeconfig = read_kubeconfig()?;
     Ok(())
 }
 
 pub async fn get_context_names() -> Result<Vec<String>> {
-    let kubeconfig = kube::config::Kubeconfig::read_from(get_kubeconfig_path())?;
+    let kubeconfig = read_kubeconfig()?;
     Ok(kubeconfig.contexts.iter().map(|c| c.name.clone()).collect())
 }
 
-pub async fn test_connection() -> Result<()> {
-    let client = create_client().await?;
-    // Try to get server version as a connection test
-    let _namespaces: Api<Namespace> = Api::all(client);
-    Ok(())
-}
-
 pub async fn list_contexts() -> Result<Vec<KubeContext>> {
-    let kubeconfig = kube::config::Kubeconfig::read_from(get_kubeconfig_path())?;
+    let kubeconfig = read_kubeconfig()?;
     let current_context = kubeconfig.current_context.clone();
 
     let contexts: Vec<KubeContext> = kubeconfig
@@ -643,39 +686,69 @@ pub async fn list_contexts() -> Result<Vec<KubeContext>> {
 }
 
 pub async fn get_current_context_name() -> Result<String> {
-    let kubeconfig = kube::config::Kubeconfig::read_from(get_kubeconfig_path())?;
+    let kubeconfig = read_kubeconfig()?;
     kubeconfig
         .current_context
-        .ok_or_else(|| AppError::Custom("No current context set".into()))
+        .ok_or_else(|| AppError::NotFound("No current context set".into()))
+}
+
+/// Read a single kubeconfig file as raw YAML so writes keep fields we don't model
+pub fn read_kubeconfig_document(path: &std::path::Path) -> Result<serde_yaml::Value> {
+    let content = std::fs::read_to_string(path)?;
+    let doc: serde_yaml::Value = serde_yaml::from_str(&content)?;
+    Ok(match doc {
+        serde_yaml::Value::Null => serde_yaml::Value::Mapping(serde_yaml::Mapping::new()),
+        other => other,
+    })
+}
+
+/// Pick the file `current-context` is written to, the same way kubectl does:
+/// the first file that already sets it, otherwise the first file in the list.
+fn get_current_context_file() -> Result<PathBuf> {
+    let paths = get_kubeconfig_paths();
+    for path in &paths {
+        let doc = read_kubeconfig_document(path)?;
+        let has_current = doc
+            .get("current-context")
+            .and_then(|v| v.as_str())
+            .map(|v| !v.is_empty())
+            .unwrap_or(false);
+        if has_current {
+            return Ok(path.clone());
+        }
+    }
+    paths
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::NotFound("No kubeconfig file found".to_string()))
 }
 
 pub async fn switch_to_context(context_name: &str) -> Result<()> {
-    let config_path = get_kubeconfig_path();
-    let mut kubeconfig = kube::config::Kubeconfig::read_from(&config_path)?;
+    let kubeconfig = read_kubeconfig()?;
 
     // Verify context exists
     if !kubeconfig.contexts.iter().any(|c| c.name == context_name) {
-        return Err(AppError::Custom(format!(
+        return Err(AppError::NotFound(format!(
             "Context '{}' not found",
             context_name
         )));
     }
 
-    kubeconfig.current_context = Some(context_name.to_string());
+    let config_path = get_current_context_file()?;
+    let mut doc = read_kubeconfig_document(&config_path)?;
+    let mapping = doc
+        .as_mapping_mut()
+        .ok_or_else(|| AppError::Custom(format!("Invalid kubeconfig: {}", config_path.display())))?;
+    mapping.insert(
+        serde_yaml::Value::String("current-context".to_string()),
+        serde_yaml::Value::String(context_name.to_string()),
+    );
 
-    let yaml = serde_yaml::to_string(&kubeconfig)
-        .map_err(|e| AppError::Custom(format!("Failed to serialize config: {}", e)))?;
-    std::fs::write(&config_path, yaml)?;
+    crate::kubeconfig::write_kubeconfig_document(&config_path, &doc)?;
 
     Ok(())
 }
 
-pub async fn create_client() -> Result<Client> {
-    let config = Config::infer().await?;
-    let client = Client::try_from(config)?;
-    Ok(client)
-}
-
 pub async fn list_namespaces(client: &Client) -> Result<Vec<String>> {
     let namespaces: Api<Namespace> = Api::all(client.clone());
     let ns_list = namespaces.list(&ListParams::default()).await?;
@@ -1054,6 +1127,51 @@ pub async fn delete_pod_by_name(client: &Client, namespace: &str, pod_name: &str
     Ok(())
 }
 
+/// Evict a pod through the Eviction API, which refuses (rather than waits) when a
+/// PodDisruptionBudget doesn't allow the disruption
+pub async fn evict_pod_by_name(
+    client: &Client,
+    namespace: &str,
+    pod_name: &str,
+    grace_period_seconds: Option<u32>,
+) -> Result<()> {
+    let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    let params = kube::api::EvictParams {
+        delete_options: Some(DeleteParams {
+            grace_period_seconds,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    match pods.evict(pod_name, &params).await {
+        Ok(_) => Ok(()),
+        Err(kube::Error::Api(response)) if response.code == 429 => Err(AppError::Conflict(format!(
+            "Eviction of {} blocked by a PodDisruptionBudget: {}",
+            pod_name, response.message
+        ))),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Delete a pod stuck in Terminating with grace period 0. The API object is removed
+/// without waiting for the kubelet to confirm the containers stopped.
+pub async fn force_delete_pod_by_name(client: &Client, namespace: &str, pod_name: &str) -> Result<()> {
+    let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    let pod = pods.get(pod_name).await?;
+    if pod.metadata.deletion_timestamp.is_none() {
+        return Err(AppError::Invalid(format!(
+            "Pod {} is not terminating: delete or evict it normally first",
+            pod_name
+        )));
+    }
+    let params = DeleteParams {
+        grace_period_seconds: Some(0),
+        ..Default::default()
+    };
+    pods.delete(pod_name, &params).await?;
+    Ok(())
+}
+
 pub async fn list_deployments(client: &Client, namespace: Option<&str>) -> Result<Vec<DeploymentInfo>> {
     let deployments: Api<Deployment> = match namespace {
         Some(ns) => Api::namespaced(client.clone(), ns),
@@ -1124,6 +1242,20 @@ pub async fn restart_deployment(client: &Client, namespace: &str, name: &str) ->
     Ok(())
 }
 
+/// Pause or resume a Deployment's rollout. Template changes made while paused
+/// are rolled out together on resume.
+pub async fn set_deployment_paused(client: &Client, namespace: &str, name: &str, paused: bool) -> Result<()> {
+    let deployments: Api<Deployment> = Api::namespaced(client.clone(), namespace);
+    let patch = serde_json::json!({
+        "spec": {
+            "paused": paused
+        }
+    });
+
+    deployments.patch(name, &PatchParams::default(), &Patch::Merge(&patch)).await?;
+    Ok(())
+}
+
 pub async fn get_deployment_detail(client: &Client, namespace: &str, name: &str) -> Result<DeploymentDetail> {
     let deployments: Api<Deployment> = Api::namespaced(client.clone(), namespace);
     let deploy = deployments.get(name).await?;
@@ -1142,6 +1274,10 @@ pub async fn get_deployment_detail(client: &Client, namespace: &str, name: &str)
                 .collect()
         })
         .unwrap_or_default();
+    let container_names = spec
+        .and_then(|s| s.template.spec.as_ref())
+        .map(|ps| ps.containers.iter().map(|c| c.name.clone()).collect())
+        .unwrap_or_default();
 
     // Get strategy
     let strategy = spec
@@ -1190,11 +1326,13 @@ pub async fn get_deployment_detail(client: &Client, namespace: &str, name: &str)
         updated_replicas: status.and_then(|s| s.updated_replicas).unwrap_or(0),
         available_replicas: status.and_then(|s| s.available_replicas).unwrap_or(0),
         strategy,
+        paused: spec.and_then(|s| s.paused).unwrap_or(false),
         min_ready_seconds: spec.and_then(|s| s.min_ready_seconds).unwrap_or(0),
         revision_history_limit: spec.and_then(|s| s.revision_history_limit),
         selector,
         conditions,
         container_images,
+        container_names,
     })
 }
 
@@ -1404,6 +1542,42 @@ pub async fn restart_statefulset(client: &Client, namespace: &str, name: &str) -
     Ok(())
 }
 
+/// Set the rolling update partition: only pods with an ordinal >= `partition` are
+/// updated, so lowering it step by step gives a staged (canary) rollout
+pub async fn set_statefulset_partition(client: &Client, namespace: &str, name: &str, partition: i32) -> Result<()> {
+    if partition < 0 {
+        return Err(AppError::Invalid("Partition must not be negative".to_string()));
+    }
+
+    let statefulsets: Api<StatefulSet> = Api::namespaced(client.clone(), namespace);
+    let sts = statefulsets.get(name).await?;
+    let strategy = sts
+        .spec
+        .as_ref()
+        .and_then(|s| s.update_strategy.as_ref())
+        .and_then(|s| s.type_.as_deref());
+    if strategy == Some("OnDelete") {
+        return Err(AppError::Invalid(format!(
+            "StatefulSet {} uses the OnDelete strategy: partitions only apply to RollingUpdate",
+            name
+        )));
+    }
+
+    let patch = serde_json::json!({
+        "spec": {
+            "updateStrategy": {
+                "type": "RollingUpdate",
+                "rollingUpdate": {
+                    "partition": partition
+                }
+            }
+        }
+    });
+
+    statefulsets.patch(name, &PatchParams::default(), &Patch::Merge(&patch)).await?;
+    Ok(())
+}
+
 pub async fn get_statefulset_detail(client: &Client, namespace: &str, name: &str) -> Result<StatefulSetDetail> {
     let statefulsets: Api<StatefulSet> = Api::namespaced(client.clone(), namespace);
     let sts = statefulsets.get(name).await?;
@@ -1422,6 +1596,10 @@ pub async fn get_statefulset_detail(client: &Client, namespace: &str, name: &str
                 .collect()
         })
         .unwrap_or_default();
+    let container_names = spec
+        .and_then(|s| s.template.spec.as_ref())
+        .map(|ps| ps.containers.iter().map(|c| c.name.clone()).collect())
+        .unwrap_or_default();
 
     // Get update strategy
     let update_strategy = spec
@@ -1476,10 +1654,15 @@ pub async fn get_statefulset_detail(client: &Client, namespace: &str, name: &str
         service_name: spec.map(|s| s.service_name.clone()).unwrap_or_default(),
         pod_management_policy,
         update_strategy,
+        partition: spec
+            .and_then(|s| s.update_strategy.as_ref())
+            .and_then(|s| s.rolling_update.as_ref())
+            .and_then(|r| r.partition),
         revision_history_limit: spec.and_then(|s| s.revision_history_limit),
         selector,
         conditions,
         container_images,
+        container_names,
     })
 }
 
@@ -1699,60 +1882,7 @@ pub async fn list_jobs(client: &Client, namespace: Option<&str>) -> Result<Vec<J
 
     let job_list = jobs.list(&ListParams::default()).await?;
 
-    let job_infos: Vec<JobInfo> = job_list
-        .items
-        .iter()
-        .map(|job| {
-            let metadata = &job.metadata;
-            let spec = job.spec.as_ref();
-            let status = job.status.as_ref();
-
-            let completions_spec = spec.and_then(|s| s.completions).unwrap_or(1);
-            let succeeded = status.and_then(|s| s.succeeded).unwrap_or(0);
-            let failed = status.and_then(|s| s.failed).unwrap_or(0);
-            let active = status.and_then(|s| s.active).unwrap_or(0);
-
-            let job_status = if succeeded >= completions_spec {
-                "Complete".to_string()
-            } else if failed > 0 {
-                "Failed".to_string()
-            } else if active > 0 {
-                "Running".to_string()
-            } else {
-                "Pending".to_string()
-            };
-
-            let duration = status
-                .and_then(|s| s.start_time.as_ref())
-                .map(|start| {
-                    let end = status
-                        .and_then(|s| s.completion_time.as_ref())
-                        .and_then(|t| chrono::DateTime::parse_from_rfc3339(&t.0.to_rfc3339()).ok())
-                        .unwrap_or_else(|| chrono::Utc::now().into());
-                    let start_dt = chrono::DateTime::parse_from_rfc3339(&start.0.to_rfc3339())
-                        .unwrap_or_else(|_| chrono::Utc::now().into());
-                    let dur = end.signed_duration_since(start_dt);
-                    if dur.num_hours() > 0 {
-                        format!("{}h{}m", dur.num_hours(), dur.num_minutes() % 60)
-                    } else if dur.num_minutes() > 0 {
-                        format!("{}m{}s", dur.num_minutes(), dur.num_seconds() % 60)
-                    } else {
-                        format!("{}s", dur.num_seconds())
-                    }
-                });
-
-            JobInfo {
-                name: metadata.name.clone().unwrap_or_default(),
-                namespace: metadata.namespace.clone().unwrap_or_default(),
-                completions: format!("{}/{}", succeeded, completions_spec),
-                duration,
-                age: get_age(metadata.creation_timestamp.as_ref()),
-                status: job_status,
-            }
-        })
-        .collect();
-
-    Ok(job_infos)
+    Ok(job_list.items.iter().map(job_to_job_info).collect())
 }
 
 pub async fn list_cronjobs(client: &Client, namespace: Option<&str>) -> Result<Vec<CronJobInfo>> {
@@ -2677,24 +2807,22 @@ pub async fn get_metrics(client: &Client) -> Result<ClusterMetrics> {
     })
 }
 
-pub async fn get_pulse_metrics(client: &Client, namespace: Option<&str>) -> Result<PulseMetrics> {
+pub async fn get_pulse_metrics(
+    client: &Client,
+    context_name: &str,
+    k8s_version: &str,
+    namespace: Option<&str>,
+) -> Result<PulseMetrics> {
     // Get cluster info from kubeconfig
-    let kubeconfig = kube::config::Kubeconfig::read_from(get_kubeconfig_path())?;
-    let current_context_name = kubeconfig.current_context.clone().unwrap_or_default();
-    let current_ctx = kubeconfig.contexts.iter().find(|c| c.name == current_context_name);
+    let kubeconfig = read_kubeconfig()?;
+    let current_ctx = kubeconfig.contexts.iter().find(|c| c.name == context_name);
     let context_info = current_ctx.and_then(|c| c.context.as_ref());
 
     let cluster_name = context_info.map(|c| c.cluster.clone()).unwrap_or_default();
     let user_name = context_info.and_then(|c| c.user.clone()).unwrap_or_default();
 
-    // Get K8s version from nodes
     let nodes_api: Api<Node> = Api::all(client.clone());
     let nodes = nodes_api.list(&ListParams::default()).await?;
-    let k8s_version = nodes.items.first()
-        .and_then(|n| n.status.as_ref())
-        .and_then(|s| s.node_info.as_ref())
-        .map(|info| info.kubelet_version.clone())
-        .unwrap_or_else(|| "Unknown".to_string());
 
     // Create APIs based on namespace filter
     let pods_api: Api<Pod> = match namespace {
@@ -2875,10 +3003,10 @@ pub async fn get_pulse_metrics(client: &Client, namespace: Option<&str>) -> Resu
     }).count() as i32;
 
     Ok(PulseMetrics {
-        context: current_context_name,
+        context: context_name.to_string(),
         cluster: cluster_name,
         user: user_name,
-        k8s_version,
+        k8s_version: k8s_version.to_string(),
         pods: ResourceCount { ok: pods_ok, fail: pods_fail },
         deployments: ResourceCount { ok: deployments_ok, fail: deployments_fail },
         statefulsets: ResourceCount { ok: statefulsets_ok, fail: statefulsets_fail },
@@ -2961,6 +3089,61 @@ fn get_container_state(status: Option<&k8s_openapi::api::core::v1::ContainerStat
         .unwrap_or_else(|| "Unknown".to_string())
 }
 
+fn job_to_job_info(job: &Job) -> JobInfo {
+    let metadata = &job.metadata;
+    let spec = job.spec.as_ref();
+    let status = job.status.as_ref();
+
+    let completions_spec = spec.and_then(|s| s.completions).unwrap_or(1);
+    let succeeded = status.and_then(|s| s.succeeded).unwrap_or(0);
+    let failed = status.and_then(|s| s.failed).unwrap_or(0);
+    let active = status.and_then(|s| s.active).unwrap_or(0);
+
+    // Finished conditions are authoritative; failed pods alone may still be retried
+    let finished = status
+        .and_then(|s| s.conditions.as_ref())
+        .and_then(|c| c.iter().find(|c| c.status == "True" && (c.type_ == "Complete" || c.type_ == "Failed")));
+    let job_status = if let Some(condition) = finished {
+        condition.type_.clone()
+    } else if succeeded >= completions_spec {
+        "Complete".to_string()
+    } else if failed > 0 {
+        "Failed".to_string()
+    } else if active > 0 {
+        "Running".to_string()
+    } else {
+        "Pending".to_string()
+    };
+
+    let duration = status
+        .and_then(|s| s.start_time.as_ref())
+        .map(|start| {
+            let end = status
+                .and_then(|s| s.completion_time.as_ref())
+                .and_then(|t| chrono::DateTime::parse_from_rfc3339(&t.0.to_rfc3339()).ok())
+                .unwrap_or_else(|| chrono::Utc::now().into());
+            let start_dt = chrono::DateTime::parse_from_rfc3339(&start.0.to_rfc3339())
+                .unwrap_or_else(|_| chrono::Utc::now().into());
+            let dur = end.signed_duration_since(start_dt);
+            if dur.num_hours() > 0 {
+                format!("{}h{}m", dur.num_hours(), dur.num_minutes() % 60)
+            } else if dur.num_minutes() > 0 {
+                format!("{}m{}s", dur.num_minutes(), dur.num_seconds() % 60)
+            } else {
+                format!("{}s", dur.num_seconds())
+            }
+        });
+
+    JobInfo {
+        name: metadata.name.clone().unwrap_or_default(),
+        namespace: metadata.namespace.clone().unwrap_or_default(),
+        completions: format!("{}/{}", succeeded, completions_spec),
+        duration,
+        age: get_age(metadata.creation_timestamp.as_ref()),
+        status: job_status,
+    }
+}
+
 fn pod_to_pod_info(pod: &Pod) -> PodInfo {
     let metadata = &pod.metadata;
     let spec = pod.spec.as_ref();
@@ -3008,7 +3191,7 @@ fn pod_to_pod_info(pod: &Pod) -> PodInfo {
     }
 }
 
-fn get_age(timestamp: Option<&k8s_openapi::apimachinery::pkg::apis::meta::v1::Time>) -> String {
+pub fn get_age(timestamp: Option<&k8s_openapi::apimachinery::pkg::apis::meta::v1::Time>) -> String {
     timestamp
         .map(|ts| {
             let now = chrono::Utc::now();
@@ -3916,6 +4099,129 @@ pub async fn get_cronjob_events(client: &Client, namespace: &str, name: &str) ->
     }).collect())
 }
 
+/// A Job created by a CronJob, scheduled or run by hand
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CronJobJob {
+    #[serde(flatten)]
+    pub job: JobInfo,
+    pub creation_timestamp: Option<String>,
+    /// Created with "run now" rather than by the schedule
+    pub manual: bool,
+    /// Reason and message of the Failed condition
+    pub failure: Option<String>,
+}
+
+const CRONJOB_INSTANTIATE_ANNOTATION: &str = "cronjob.kubernetes.io/instantiate";
+
+/// `<base>-<infix>-<5 random chars>`, shortening `base` so the name fits the
+/// job-name pod label, which is limited to 63 characters
+pub fn generated_job_name(base: &str, infix: &str) -> String {
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    let prefix: String = base.chars().take(63 - infix.len() - 2 - 5).collect();
+    format!("{}-{}-{}", prefix.trim_end_matches(['-', '.']), infix, &suffix[..5])
+}
+
+/// Create a Job from the CronJob's job template right away, like
+/// `kubectl create job --from=cronjob/<name>`. Returns the new Job's name.
+pub async fn trigger_cronjob(client: &Client, namespace: &str, name: &str) -> Result<String> {
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ObjectMeta, OwnerReference};
+
+    let cronjobs: Api<CronJob> = Api::namespaced(client.clone(), namespace);
+    let cj = cronjobs.get(name).await?;
+    let template = cj.spec.map(|s| s.job_template).unwrap_or_default();
+    let template_meta = template.metadata.unwrap_or_default();
+
+    let mut annotations = template_meta.annotations.unwrap_or_default();
+    annotations.insert(CRONJOB_INSTANTIATE_ANNOTATION.to_string(), "manual".to_string());
+
+    let job_name = generated_job_name(name, "manual");
+
+    let job = Job {
+        metadata: ObjectMeta {
+            name: Some(job_name.clone()),
+            namespace: Some(namespace.to_string()),
+            labels: template_meta.labels,
+            annotations: Some(annotations),
+            owner_references: Some(vec![OwnerReference {
+                api_version: "batch/v1".to_string(),
+                kind: "CronJob".to_string(),
+                name: name.to_string(),
+                uid: cj.metadata.uid.unwrap_or_default(),
+                controller: Some(true),
+                block_owner_deletion: Some(true),
+            }]),
+            ..Default::default()
+        },
+        spec: template.spec,
+        status: None,
+    };
+
+    let jobs: Api<Job> = Api::namespaced(client.clone(), namespace);
+    jobs.create(&PostParams::default(), &job).await?;
+    Ok(job_name)
+}
+
+pub async fn set_cronjob_suspended(client: &Client, namespace: &str, name: &str, suspend: bool) -> Result<()> {
+    let cronjobs: Api<CronJob> = Api::namespaced(client.clone(), namespace);
+    let patch = serde_json::json!({
+        "spec": {
+            "suspend": suspend
+        }
+    });
+    cronjobs.patch(name, &PatchParams::default(), &Patch::Merge(&patch)).await?;
+    Ok(())
+}
+
+/// Jobs controlled by the CronJob, newest first
+pub async fn get_cronjob_jobs(client: &Client, namespace: &str, name: &str) -> Result<Vec<CronJobJob>> {
+    let cronjobs: Api<CronJob> = Api::namespaced(client.clone(), namespace);
+    let uid = cronjobs.get(name).await?.metadata.uid.unwrap_or_default();
+
+    let jobs: Api<Job> = Api::namespaced(client.clone(), namespace);
+    let mut owned: Vec<Job> = jobs
+        .list(&ListParams::default())
+        .await?
+        .items
+        .into_iter()
+        .filter(|job| {
+            job.metadata
+                .owner_references
+                .as_ref()
+                .is_some_and(|refs| refs.iter().any(|r| r.uid == uid && r.controller == Some(true)))
+        })
+        .collect();
+    owned.sort_by(|a, b| b.metadata.creation_timestamp.cmp(&a.metadata.creation_timestamp));
+
+    Ok(owned
+        .iter()
+        .map(|job| {
+            let failure = job
+                .status
+                .as_ref()
+                .and_then(|s| s.conditions.as_ref())
+                .and_then(|c| c.iter().find(|c| c.type_ == "Failed" && c.status == "True"))
+                .map(|c| {
+                    format!(
+                        "{}: {}",
+                        c.reason.as_deref().unwrap_or("Failed"),
+                        c.message.as_deref().unwrap_or_default()
+                    )
+                });
+            CronJobJob {
+                job: job_to_job_info(job),
+                creation_timestamp: job.metadata.creation_timestamp.as_ref().map(|t| t.0.to_rfc3339()),
+                manual: job
+                    .metadata
+                    .annotations
+                    .as_ref()
+                    .and_then(|a| a.get(CRONJOB_INSTANTIATE_ANNOTATION))
+                    .is_some_and(|v| v == "manual"),
+                failure,
+            }
+        })
+        .collect())
+}
+
 // ============ DaemonSet Detail ============
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -3959,6 +4265,27 @@ pub struct DaemonSetEvent {
     pub source: String,
 }
 
+pub async fn restart_daemonset(client: &Client, namespace: &str, name: &str) -> Result<()> {
+    let daemonsets: Api<DaemonSet> = Api::namespaced(client.clone(), namespace);
+
+    // Rollout restart is done by updating the pod template annotation
+    let now = chrono::Utc::now().to_rfc3339();
+    let patch = serde_json::json!({
+        "spec": {
+            "template": {
+                "metadata": {
+                    "annotations": {
+                        "kubectl.kubernetes.io/restartedAt": now
+                    }
+                }
+            }
+        }
+    });
+
+    daemonsets.patch(name, &PatchParams::default(), &Patch::Merge(&patch)).await?;
+    Ok(())
+}
+
 pub async fn get_daemonset_detail(client: &Client, namespace: &str, name: &str) -> Result<DaemonSetDetail> {
     let daemonsets: Api<DaemonSet> = Api::namespaced(client.clone(), namespace);
     let ds = daemonsets.get(name).await?;
@@ -4098,6 +4425,47 @@ pub struct ReplicaSetEvent {
     pub source: String,
 }
 
+pub async fn scale_replicaset(client: &Client, namespace: &str, name: &str, replicas: i32) -> Result<()> {
+    let replicasets: Api<ReplicaSet> = Api::namespaced(client.clone(), namespace);
+
+    // The owning Deployment would immediately scale it back
+    let rs = replicasets.get(name).await?;
+    if let Some(owner) = rs
+        .metadata
+        .owner_references
+        .iter()
+        .flatten()
+        .find(|o| o.controller == Some(true) && o.kind == "Deployment")
+    {
+        return Err(AppError::Invalid(format!(
+            "ReplicaSet {} is managed by Deployment {}: scale the Deployment instead",
+            name, owner.name
+        )));
+    }
+
+    let patch = serde_json::json!({
+        "spec": {
+            "replicas": replicas
+        }
+    });
+
+    replicasets.patch(name, &PatchParams::default(), &Patch::Merge(&patch)).await?;
+    Ok(())
+}
+
+pub async fn scale_replication_controller(client: &Client, namespace: &str, name: &str, replicas: i32) -> Result<()> {
+    let controllers: Api<ReplicationController> = Api::namespaced(client.clone(), namespace);
+
+    let patch = serde_json::json!({
+        "spec": {
+            "replicas": replicas
+        }
+    });
+
+    controllers.patch(name, &PatchParams::default(), &Patch::Merge(&patch)).await?;
+    Ok(())
+}
+
 pub async fn get_replicaset_detail(client: &Client, namespace: &str, name: &str) -> Result<ReplicaSetDetail> {
     let replicasets: Api<ReplicaSet> = Api::namespaced(client.clone(), namespace);
     let rs = replicasets.get(name).await?;
@@ -5065,14 +5433,16 @@ pub async fn get_node_pods(client: &Client, node_name: &str) -> Result<Vec<PodIn
 
 // ============ Node Taint Operations ============
 
-fn validate_taint_key(key: &str) -> Result<()> {
+/// Check a label, annotation or taint key: an optional DNS subdomain prefix and
+/// `/`, then a name of up to 63 characters. `what` starts the error messages.
+pub(crate) fn validate_qualified_name(key: &str, what: &str) -> Result<()> {
     if key.is_empty() {
-        return Err(AppError::Custom("Taint key is required".to_string()));
+        return Err(AppError::Invalid(format!("{} is required", what)));
     }
 
     let parts: Vec<&str> = key.split('/').collect();
     if parts.len() > 2 {
-        return Err(AppError::Custom("Taint key can only have one '/' separator".to_string()));
+        return Err(AppError::Invalid(format!("{} '{}' can only have one '/' separator", what, key)));
     }
 
     let name = if parts.len() == 2 { parts[1] } else { parts[0] };
@@ -5080,43 +5450,49 @@ fn validate_taint_key(key: &str) -> Result<()> {
 
     // Validate name
     if name.is_empty() {
-        return Err(AppError::Custom("Taint key name is required".to_string()));
+        return Err(AppError::Invalid(format!("{} '{}' needs a name after the prefix", what, key)));
     }
     if name.len() > 63 {
-        return Err(AppError::Custom("Taint key name must be 63 characters or less".to_string()));
+        return Err(AppError::Invalid(format!("{} name '{}' must be 63 characters or less", what, name)));
     }
 
     let name_regex = regex::Regex::new(r"^[a-zA-Z0-9]([a-zA-Z0-9._-]*[a-zA-Z0-9])?$|^[a-zA-Z0-9]$").unwrap();
     if !name_regex.is_match(name) {
-        return Err(AppError::Custom("Taint key name must start/end with alphanumeric, can contain -, _, .".to_string()));
+        return Err(AppError::Invalid(format!(
+            "{} name '{}' must start/end with alphanumeric, can contain -, _, .",
+            what, name
+        )));
     }
 
     // Validate prefix if present
     if let Some(p) = prefix {
         if p.len() > 253 {
-            return Err(AppError::Custom("Taint key prefix must be 253 characters or less".to_string()));
+            return Err(AppError::Invalid(format!("{} prefix must be 253 characters or less", what)));
         }
         let prefix_regex = regex::Regex::new(r"^[a-z0-9]([-a-z0-9]*[a-z0-9])?(\.[a-z0-9]([-a-z0-9]*[a-z0-9])?)*$").unwrap();
         if !prefix_regex.is_match(p) {
-            return Err(AppError::Custom("Taint key prefix must be a valid DNS subdomain".to_string()));
+            return Err(AppError::Invalid(format!("{} prefix '{}' must be a valid DNS subdomain", what, p)));
         }
     }
 
     Ok(())
 }
 
-fn validate_taint_value(value: Option<&str>) -> Result<()> {
-    if let Some(v) = value {
-        if v.is_empty() {
-            return Ok(()); // Empty is fine
-        }
-        if v.len() > 63 {
-            return Err(AppError::Custom("Taint value must be 63 characters or less".to_string()));
-        }
-        let value_regex = regex::Regex::new(r"^[a-zA-Z0-9]([a-zA-Z0-9._-]*[a-zA-Z0-9])?$|^[a-zA-Z0-9]$").unwrap();
-        if !value_regex.is_match(v) {
-            return Err(AppError::Custom("Taint value must start/end with alphanumeric, can contain -, _, .".to_string()));
-        }
+/// Check a label or taint value: empty, or up to 63 characters starting and
+/// ending with an alphanumeric. `what` starts the error messages.
+pub(crate) fn validate_label_value(value: &str, what: &str) -> Result<()> {
+    if value.is_empty() {
+        return Ok(()); // Empty is fine
+    }
+    if value.len() > 63 {
+        return Err(AppError::Invalid(format!("{} '{}' must be 63 characters or less", what, value)));
+    }
+    let value_regex = regex::Regex::new(r"^[a-zA-Z0-9]([a-zA-Z0-9._-]*[a-zA-Z0-9])?$|^[a-zA-Z0-9]$").unwrap();
+    if !value_regex.is_match(value) {
+        return Err(AppError::Invalid(format!(
+            "{} '{}' must start/end with alphanumeric, can contain -, _, .",
+            what, value
+        )));
     }
     Ok(())
 }
@@ -5124,7 +5500,7 @@ fn validate_taint_value(value: Option<&str>) -> Result<()> {
 fn validate_taint_effect(effect: &str) -> Result<()> {
     match effect {
         "NoSchedule" | "PreferNoSchedule" | "NoExecute" => Ok(()),
-        _ => Err(AppError::Custom(format!(
+        _ => Err(AppError::Invalid(format!(
             "Invalid taint effect '{}'. Must be NoSchedule, PreferNoSchedule, or NoExecute",
             effect
         ))),
@@ -5139,8 +5515,10 @@ pub async fn add_node_taint(
     effect: &str,
 ) -> Result<()> {
     // Validate inputs
-    validate_taint_key(key)?;
-    validate_taint_value(value)?;
+    validate_qualified_name(key, "Taint key")?;
+    if let Some(value) = value {
+        validate_label_value(value, "Taint value")?;
+    }
     validate_taint_effect(effect)?;
 
     let nodes: Api<Node> = Api::all(client.clone());
@@ -5153,7 +5531,7 @@ pub async fn add_node_taint(
 
     // Check if taint with same key and effect already exists
     if taints.iter().any(|t| t.key == key && t.effect == effect) {
-        return Err(AppError::Custom(format!(
+        return Err(AppError::Conflict(format!(
             "Taint with key '{}' and effect '{}' already exists on node '{}'",
             key, effect, name
         )));
@@ -5319,7 +5697,7 @@ pub async fn get_serviceaccount_events(client: &Client, namespace: &str, name: &
 
 /// Create a client for a specific context
 pub async fn create_client_for_context(context_name: &str) -> Result<Client> {
-    let kubeconfig = kube::config::Kubeconfig::read_from(get_kubeconfig_path())?;
+    let kubeconfig = read_kubeconfig()?;
 
     // Build config options for the specific context
     let options = kube::config::KubeConfigOptions {
@@ -5331,44 +5709,3 @@ pub async fn create_client_for_context(context_name: &str) -> Result<Client> {
     let client = Client::try_from(config)?;
     Ok(client)
 }
-
-// ============ YAML Apply Function ============
-
-/// Apply a YAML manifest to the cluster using kubectl
-/// This is the most reliable way to handle all resource types and edge cases
-pub async fn apply_yaml(context_name: &str, yaml_content: &str) -> Result<String> {
-    use std::process::Command;
-    use std::io::Write;
-
-    // Create a temporary file for the YAML
-    let temp_dir = std::env::temp_dir();
-    let temp_file = temp_dir.join(format!("apex-kube-apply-{}.yaml", std::process::id()));
-
-    // Write YAML to temp file
-    let mut file = std::fs::File::create(&temp_file)
-        .map_err(|e| AppError::Custom(format!("Failed to create temp file: {}", e)))?;
-    file.write_all(yaml_content.as_bytes())
-        .map_err(|e| AppError::Custom(format!("Failed to write YAML: {}", e)))?;
-    drop(file);
-
-    // Run kubectl apply
-    let output = Command::new("kubectl")
-        .args([
-            "--context", context_name,
-            "apply",
-            "-f", temp_file.to_str().unwrap(),
-        ])
-        .output()
-        .map_err(|e| AppError::Custom(format!("Failed to execute kubectl: {}", e)))?;
-
-    // Clean up temp file
-    let _ = std::fs::remove_file(&temp_file);
-
-    if output.status.success() {
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        Ok(stdout)
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        Err(AppError::Custom(format!("kubectl apply failed: {}", stderr)))
-    }
-}
@@ -1,12 +1,28 @@
+mod apply;
+mod audit;
 mod commands;
+mod context;
+mod cron;
+mod diagnostics;
+mod discovery;
+mod drain;
 mod error;
+mod kubeconfig;
 mod kubernetes;
 mod portforward;
 mod pty;
+mod resources;
+mod rollout;
+mod settings;
 mod watch;
+mod workloads;
 
+use audit::AuditLog;
+use context::ContextManager;
 use portforward::PortForwardManager;
 use pty::PtyManager;
+use settings::SettingsManager;
+use tauri::Manager;
 use watch::WatchManager;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -120,9 +136,16 @@ pub fn run() {
 
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .manage(ContextManager::new())
         .manage(PtyManager::new())
         .manage(PortForwardManager::new())
         .manage(WatchManager::new())
+        .setup(|app| {
+            let data_dir = app.path().app_data_dir()?;
+            app.manage(SettingsManager::load(data_dir.clone()));
+            app.manage(AuditLog::new(data_dir));
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // Startup checks
             commands::check_kubeconfig,
@@ -133,14 +156,28 @@ pub fn run() {
             commands::get_contexts,
             commands::get_current_context,
             commands::switch_context,
+            commands::import_kubeconfig,
+            commands::rename_context,
+            commands::delete_context,
+            commands::set_context_namespace,
+            commands::list_kubeconfig_backups,
+            commands::restore_kubeconfig_backup,
+            commands::get_app_settings,
+            commands::set_read_only_mode,
+            commands::set_context_settings,
+            commands::get_audit_log,
             // Resources
             commands::get_namespaces,
             commands::get_pods,
             commands::get_pod_logs,
             commands::delete_pod,
+            commands::evict_pod,
+            commands::force_delete_pod,
             commands::get_deployments,
             commands::scale_deployment,
             commands::restart_deployment,
+            commands::pause_deployment,
+            commands::resume_deployment,
             commands::get_deployment_detail,
             commands::get_deployment_yaml,
             commands::get_deployment_events,
@@ -148,16 +185,20 @@ pub fn run() {
             commands::get_statefulsets,
             commands::scale_statefulset,
             commands::restart_statefulset,
+            commands::set_statefulset_partition,
             commands::get_statefulset_detail,
             commands::get_statefulset_yaml,
             commands::get_statefulset_events,
             commands::get_statefulset_pods,
             commands::get_daemonsets,
+            commands::restart_daemonset,
             commands::get_daemonset_detail,
             commands::get_daemonset_yaml,
             commands::get_daemonset_events,
             commands::get_daemonset_pods,
             commands::get_replicasets,
+            commands::scale_replicaset,
+            commands::scale_replication_controller,
             commands::get_replicaset_detail,
             commands::get_replicaset_yaml,
             commands::get_replicaset_events,
@@ -167,10 +208,15 @@ pub fn run() {
             commands::get_job_yaml,
             commands::get_job_events,
             commands::get_job_pods,
+            commands::rerun_job,
             commands::get_cronjobs,
             commands::get_cronjob_detail,
             commands::get_cronjob_yaml,
             commands::get_cronjob_events,
+            commands::get_cronjob_jobs,
+            commands::get_cronjob_schedule,
+            commands::trigger_cronjob,
+            commands::set_cronjob_suspended,
             commands::get_services,
             commands::get_service_detail,
             commands::get_service_yaml,
@@ -222,6 +268,7 @@ pub fn run() {
             commands::remove_node_taint,
             commands::cordon_node,
             commands::uncordon_node,
+            commands::drain_node,
             commands::get_service_accounts,
             commands::get_serviceaccount_detail,
             commands::get_serviceaccount_yaml,
@@ -229,6 +276,8 @@ pub fn run() {
             commands::get_events,
             commands::get_cluster_metrics,
             commands::get_pulse_metrics,
+            commands::get_server_version,
+            commands::get_api_resources,
             commands::exec_pod,
             // Resource detail
             commands::get_pod_detail,
@@ -243,6 +292,17 @@ pub fn run() {
             commands::pty_close,
             // YAML editing
             commands::apply_yaml,
+            commands::preview_apply_yaml,
+            commands::delete_resources,
+            commands::scale_resource,
+            commands::update_metadata,
+            commands::remove_finalizers,
+            commands::set_container_image,
+            commands::preview_container_resources,
+            commands::set_container_resources,
+            commands::get_rollout_history,
+            commands::diff_rollout_revisions,
+            commands::rollback_rollout,
             // Port forwarding
             commands::start_port_forward,
             commands::stop_port_forward,
@@ -255,6 +315,8 @@ pub fn run() {
             commands::watch_jobs,
             commands::watch_nodes,
             commands::watch_events,
+            commands::watch_rollout_status,
+            commands::watch_listener_ready,
             commands::stop_watch,
             commands::stop_all_watches,
         ])
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use tokio::sync::RwLock;
 use tokio::net::TcpListener;
-use kube::{Api, Client, Config};
+use kube::{Api, Client};
 use kube::api::ListParams;
 use k8s_openapi::api::core::v1::{Pod, Service};
 use serde::{Deserialize, Serialize};
@@ -115,8 +115,10 @@ impl PortForwardManager {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn start_forward(
         &self,
+        client: Client,
         context: String,
         namespace: String,
         resource_type: ResourceType,
@@ -131,7 +133,7 @@ impl PortForwardManager {
             let forwards = self.forwards.read().await;
             for (_, fwd) in forwards.iter() {
                 if fwd.info.local_port == local_port && fwd.info.status == PortForwardStatus::Active {
-                    return Err(AppError::Custom(format!(
+                    return Err(AppError::Conflict(format!(
                         "Port {} is already being forwarded by this app",
                         local_port
                     )));
@@ -141,7 +143,7 @@ impl PortForwardManager {
 
         // Check if local port is available on the system
         if !is_port_available(local_port).await {
-            return Err(AppError::Custom(format!(
+            return Err(AppError::Conflict(format!(
                 "Port {} is already in use by another application",
                 local_port
             )));
@@ -152,7 +154,7 @@ impl PortForwardManager {
             ResourceType::Pod => resource_name.clone(),
             ResourceType::Service => {
                 // Need to resolve service to a pod
-                resolve_service_to_pod(&context, &namespace, &resource_name).await?
+                resolve_service_to_pod(&client, &namespace, &resource_name).await?
             }
         };
 
@@ -191,13 +193,12 @@ impl PortForwardManager {
         // Clone what we need for the spawned task
         let forwards_ref = self.forwards.clone();
         let id_clone = id.clone();
-        let context_clone = context.clone();
         let stats_clone = stats.clone();
 
         // Spawn the port forward task
         tokio::spawn(async move {
             let result = run_port_forward(
-                context_clone,
+                client,
                 namespace,
                 pod_name,
                 local_port,
@@ -231,7 +232,7 @@ impl PortForwardManager {
             let _ = fwd.shutdown_tx.send(());
             Ok(())
         } else {
-            Err(AppError::Custom(format!("Port forward {} not found", id)))
+            Err(AppError::NotFound(format!("Port forward {} not found", id)))
         }
     }
 
@@ -259,7 +260,7 @@ impl PortForwardManager {
 
 #[allow(clippy::too_many_arguments)]
 async fn run_port_forward(
-    context: String,
+    client: Client,
     namespace: String,
     pod_name: String,
     local_port: u16,
@@ -284,10 +285,6 @@ async fn run_port_forward(
         }
     }
 
-    // Create K8s client for the specific context
-    let config = create_client_config(&context).await?;
-    let client = Client::try_from(config)?;
-
     loop {
         tokio::select! {
             // Check for shutdown signal
@@ -388,18 +385,6 @@ async fn handle_connection(
     Ok(())
 }
 
-async fn create_client_config(context_name: &str) -> Result<Config> {
-    let kubeconfig = kube::config::Kubeconfig::read_from(crate::kubernetes::get_kubeconfig_path_for_portforward())?;
-
-    let options = kube::config::KubeConfigOptions {
-        context: Some(context_name.to_string()),
-        ..Default::default()
-    };
-
-    let config = Config::from_custom_kubeconfig(kubeconfig, &options).await?;
-    Ok(config)
-}
-
 /// Check if a port is available on localhost
 async fn is_port_available(port: u16) -> bool {
     TcpListener::bind(format!("127.0.0.1:{}", port))
@@ -409,19 +394,15 @@ async fn is_port_available(port: u16) -> bool {
 
 /// Get available ports for a pod or service
 pub async fn get_resource_ports(
-    context: &str,
+    client: &Client,
     namespace: &str,
     resource_type: &ResourceType,
     resource_name: &str,
 ) -> Result<Vec<AvailablePort>> {
-    let config = create_client_config(context).await?;
-    let client = Client::try_from(config)?;
-
     match resource_type {
         ResourceType::Pod => {
-            let pods: Api<Pod> = Api::namespaced(client, namespace);
-            let pod = pods.get(resource_name).await
-                .map_err(|e| AppError::Custom(format!("Pod '{}' not found: {}", resource_name, e)))?;
+            let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+            let pod = pods.get(resource_name).await?;
 
             let mut ports = Vec::new();
             if let Some(spec) = pod.spec {
@@ -444,8 +425,7 @@ pub async fn get_resource_ports(
         }
         ResourceType::Service => {
             let services: Api<Service> = Api::namespaced(client.clone(), namespace);
-            let service = services.get(resource_name).await
-                .map_err(|e| AppError::Custom(format!("Service '{}' not found: {}", resource_name, e)))?;
+            let service = services.get(resource_name).await?;
 
             // Get selector to find pods for named port resolution
             let selector = service.spec.as_ref()
@@ -457,7 +437,7 @@ pub async fn get_resource_ports(
                     .map(|(k, v)| format!("{}={}", k, v))
                     .collect::<Vec<_>>()
                     .join(",");
-                let pods_api: Api<Pod> = Api::namespaced(client, namespace);
+                let pods_api: Api<Pod> = Api::namespaced(client.clone(), namespace);
                 if let Ok(pod_list) = pods_api.list(&ListParams::default().labels(&label_selector).limit(1)).await {
                     pod_list.items.first()
                         .and_then(|p| p.spec.as_ref())
@@ -506,24 +486,20 @@ pub async fn get_resource_ports(
 
 /// Resolve a Service to a running Pod by matching the service's selector
 async fn resolve_service_to_pod(
-    context: &str,
+    client: &Client,
     namespace: &str,
     service_name: &str,
 ) -> Result<String> {
-    let config = create_client_config(context).await?;
-    let client = Client::try_from(config)?;
-
     // Get the service
     let services: Api<Service> = Api::namespaced(client.clone(), namespace);
-    let service = services.get(service_name).await
-        .map_err(|e| AppError::Custom(format!("Service '{}' not found: {}", service_name, e)))?;
+    let service = services.get(service_name).await?;
 
     // Get the selector from the service spec
     let selector = service
         .spec
         .as_ref()
         .and_then(|s| s.selector.as_ref())
-        .ok_or_else(|| AppError::Custom(format!("Service '{}' has no selector", service_name)))?;
+        .ok_or_else(|| AppError::Invalid(format!("Service '{}' has no selector", service_name)))?;
 
     // Build label selector string
     let label_selector: String = selector
@@ -533,9 +509,8 @@ async fn resolve_service_to_pod(
         .join(",");
 
     // Find pods matching the selector
-    let pods: Api<Pod> = Api::namespaced(client, namespace);
-    let pod_list = pods.list(&ListParams::default().labels(&label_selector)).await
-        .map_err(|e| AppError::Custom(format!("Failed to list pods: {}", e)))?;
+    let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    let pod_list = pods.list(&ListParams::default().labels(&label_selector)).await?;
 
     // Find a running pod
     for pod in pod_list.items {
@@ -554,7 +529,7 @@ async fn resolve_service_to_pod(
         }
     }
 
-    Err(AppError::Custom(format!(
+    Err(AppError::NotFound(format!(
         "No running pods found for service '{}' (selector: {})",
         service_name, label_selector
     )))
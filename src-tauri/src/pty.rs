@@ -6,6 +6,8 @@ use std::thread;
 use tauri::{AppHandle, Emitter};
 use uuid::Uuid;
 
+use crate::error::{AppError, Result};
+
 /// Manages PTY sessions for terminal connections
 pub struct PtyManager {
     sessions: Arc<Mutex<HashMap<String, PtySession>>>,
@@ -27,11 +29,12 @@ impl PtyManager {
     pub fn spawn_session(
         &self,
         app: AppHandle,
+        context: Option<&str>,
         namespace: &str,
         pod_name: &str,
         container: Option<&str>,
         shell: Option<&str>,
-    ) -> Result<String, String> {
+    ) -> Result<String> {
         let pty_system = native_pty_system();
 
         // Create PTY with initial size
@@ -42,10 +45,14 @@ impl PtyManager {
                 pixel_width: 0,
                 pixel_height: 0,
             })
-            .map_err(|e| format!("Failed to open PTY: {}", e))?;
+            .map_err(|e| AppError::Custom(format!("Failed to open PTY: {}", e)))?;
 
         // Build kubectl exec command
         let mut cmd = CommandBuilder::new("kubectl");
+        // Pin the context so the session matches the window, not kubeconfig's current-context
+        if let Some(ctx) = context {
+            cmd.args(["--context", ctx]);
+        }
         cmd.args(["exec", "-it", pod_name, "-n", namespace]);
 
         if let Some(c) = container {
@@ -61,19 +68,19 @@ impl PtyManager {
         let mut child = pair
             .slave
             .spawn_command(cmd)
-            .map_err(|e| format!("Failed to spawn kubectl: {}", e))?;
+            .map_err(|e| AppError::Custom(format!("Failed to spawn kubectl: {}", e)))?;
 
         // Get writer for sending input
         let writer = pair
             .master
             .take_writer()
-            .map_err(|e| format!("Failed to get PTY writer: {}", e))?;
+            .map_err(|e| AppError::Custom(format!("Failed to get PTY writer: {}", e)))?;
 
         // Get reader for receiving output
         let mut reader = pair
             .master
             .try_clone_reader()
-            .map_err(|e| format!("Failed to get PTY reader: {}", e))?;
+            .map_err(|e| AppError::Custom(format!("Failed to get PTY reader: {}", e)))?;
 
         // Generate session ID
         let session_id = Uuid::new_v4().to_string();
@@ -131,25 +138,25 @@ impl PtyManager {
     }
 
     /// Write data to a PTY session
-    pub fn write_to_session(&self, session_id: &str, data: &str) -> Result<(), String> {
+    pub fn write_to_session(&self, session_id: &str, data: &str) -> Result<()> {
         let mut sessions = self.sessions.lock().unwrap();
         if let Some(session) = sessions.get_mut(session_id) {
             session
                 .writer
                 .write_all(data.as_bytes())
-                .map_err(|e| format!("Failed to write to PTY: {}", e))?;
+                .map_err(|e| AppError::Custom(format!("Failed to write to PTY: {}", e)))?;
             session
                 .writer
                 .flush()
-                .map_err(|e| format!("Failed to flush PTY: {}", e))?;
+                .map_err(|e| AppError::Custom(format!("Failed to flush PTY: {}", e)))?;
             Ok(())
         } else {
-            Err("Session not found".to_string())
+            Err(AppError::NotFound(format!("Terminal session {} not found", session_id)))
         }
     }
 
     /// Resize a PTY session
-    pub fn resize_session(&self, session_id: &str, rows: u16, cols: u16) -> Result<(), String> {
+    pub fn resize_session(&self, session_id: &str, rows: u16, cols: u16) -> Result<()> {
         let sessions = self.sessions.lock().unwrap();
         if let Some(session) = sessions.get(session_id) {
             session
@@ -161,20 +168,20 @@ impl PtyManager {
                     pixel_width: 0,
                     pixel_height: 0,
                 })
-                .map_err(|e| format!("Failed to resize PTY: {}", e))?;
+                .map_err(|e| AppError::Custom(format!("Failed to resize PTY: {}", e)))?;
             Ok(())
         } else {
-            Err("Session not found".to_string())
+            Err(AppError::NotFound(format!("Terminal session {} not found", session_id)))
         }
     }
 
     /// Close a PTY session
-    pub fn close_session(&self, session_id: &str) -> Result<(), String> {
+    pub fn close_session(&self, session_id: &str) -> Result<()> {
         let mut sessions = self.sessions.lock().unwrap();
         if sessions.remove(session_id).is_some() {
             Ok(())
         } else {
-            Err("Session not found".to_string())
+            Err(AppError::NotFound(format!("Terminal session {} not found", session_id)))
         }
     }
 }
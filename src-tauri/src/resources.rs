@@ -0,0 +1,497 @@
+use std::collections::BTreeMap;
+
+use futures::future::join_all;
+use k8s_openapi::api::autoscaling::v1::Scale;
+use kube::api::{Api, DeleteParams, DynamicObject, ListParams, Patch, PatchParams};
+use kube::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::audit::{object_name, AuditedObject};
+use crate::discovery::ApiResourceInfo;
+use crate::error::{AppError, Result};
+use crate::kubernetes::{validate_label_value, validate_qualified_name};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum PropagationPolicy {
+    /// Delete dependents before the owner
+    Foreground,
+    /// Delete the owner now, the garbage collector removes dependents
+    Background,
+    /// Leave dependents in place
+    Orphan,
+}
+
+impl From<PropagationPolicy> for kube::api::PropagationPolicy {
+    fn from(policy: PropagationPolicy) -> Self {
+        match policy {
+            PropagationPolicy::Foreground => kube::api::PropagationPolicy::Foreground,
+            PropagationPolicy::Background => kube::api::PropagationPolicy::Background,
+            PropagationPolicy::Orphan => kube::api::PropagationPolicy::Orphan,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeleteOptions {
+    /// Defaults to the object's own grace period
+    pub grace_period_seconds: Option<u32>,
+    /// Delete immediately (grace period 0), overriding `grace_period_seconds`
+    pub force: bool,
+    pub propagation_policy: Option<PropagationPolicy>,
+    /// Validate on the server without deleting anything
+    pub dry_run: bool,
+}
+
+impl DeleteOptions {
+    fn delete_params(&self) -> DeleteParams {
+        DeleteParams {
+            dry_run: self.dry_run,
+            grace_period_seconds: if self.force { Some(0) } else { self.grace_period_seconds },
+            propagation_policy: self.propagation_policy.map(Into::into),
+            preconditions: None,
+        }
+    }
+
+    /// Short description for the audit log
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if self.force {
+            parts.push("force".to_string());
+        } else if let Some(grace) = self.grace_period_seconds {
+            parts.push(format!("grace={}s", grace));
+        }
+        if let Some(policy) = self.propagation_policy {
+            parts.push(format!("propagation={:?}", policy));
+        }
+        if self.dry_run {
+            parts.push("dry-run".to_string());
+        }
+        parts.join(" ")
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeleteResult {
+    pub namespace: Option<String>,
+    pub name: String,
+    pub success: bool,
+    /// Deletion was accepted but the object still exists, e.g. waiting on
+    /// finalizers or foreground deletion of its dependents
+    pub pending: bool,
+    pub error: Option<AppError>,
+}
+
+impl AuditedObject for DeleteResult {
+    fn audit_name(&self) -> String {
+        object_name(self.namespace.as_deref(), &self.name)
+    }
+
+    fn audit_error(&self) -> Option<&AppError> {
+        self.error.as_ref()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum MetadataField {
+    Labels,
+    Annotations,
+}
+
+impl MetadataField {
+    fn key(&self) -> &'static str {
+        match self {
+            MetadataField::Labels => "labels",
+            MetadataField::Annotations => "annotations",
+        }
+    }
+
+    fn singular(&self) -> &'static str {
+        match self {
+            MetadataField::Labels => "Label",
+            MetadataField::Annotations => "Annotation",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetadataChanges {
+    /// Keys to add or update
+    pub set: BTreeMap<String, String>,
+    pub remove: Vec<String>,
+    /// Replace existing values; without it, changing a key that already has a
+    /// different value fails for that object
+    pub overwrite: bool,
+}
+
+impl MetadataChanges {
+    /// Short description for the audit log
+    pub fn summary(&self) -> String {
+        let mut parts: Vec<String> = self.set.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        parts.extend(self.remove.iter().map(|k| format!("{}-", k)));
+        if self.overwrite {
+            parts.push("overwrite".to_string());
+        }
+        parts.join(" ")
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct MetadataResult {
+    pub namespace: Option<String>,
+    pub name: String,
+    pub success: bool,
+    /// The object's labels or annotations after the update
+    pub values: BTreeMap<String, String>,
+    pub error: Option<AppError>,
+}
+
+impl AuditedObject for MetadataResult {
+    fn audit_name(&self) -> String {
+        object_name(self.namespace.as_deref(), &self.name)
+    }
+
+    fn audit_error(&self) -> Option<&AppError> {
+        self.error.as_ref()
+    }
+}
+
+/// Annotation keys and values together are limited to 256 KiB per object
+const MAX_ANNOTATIONS_SIZE: usize = 256 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScaleInfo {
+    /// Desired replicas after the update
+    pub replicas: i32,
+    /// Replicas observed by the controller
+    pub current_replicas: i32,
+    /// Label selector of the scaled pods, if the resource reports one
+    pub selector: Option<String>,
+}
+
+impl From<Scale> for ScaleInfo {
+    fn from(scale: Scale) -> Self {
+        Self {
+            replicas: scale.spec.and_then(|s| s.replicas).unwrap_or(0),
+            current_replicas: scale.status.as_ref().map(|s| s.replicas).unwrap_or(0),
+            selector: scale.status.and_then(|s| s.selector),
+        }
+    }
+}
+
+/// `Api<DynamicObject>` for a discovered resource. Namespaced resources without a
+/// namespace get an all-namespaces API, which only supports list and watch.
+pub fn dynamic_api(client: &Client, resource: &ApiResourceInfo, namespace: Option<&str>) -> Api<DynamicObject> {
+    let api_resource = resource.api_resource();
+    match namespace {
+        Some(ns) if resource.namespaced => Api::namespaced_with(client.clone(), ns, &api_resource),
+        _ => Api::all_with(client.clone(), &api_resource),
+    }
+}
+
+/// Delete objects of any kind, either by name or by label selector. Failures are
+/// reported per object rather than failing the whole request.
+pub async fn delete_resources(
+    client: &Client,
+    resource: &ApiResourceInfo,
+    namespace: Option<&str>,
+    names: &[String],
+    label_selector: Option<&str>,
+    all_namespaces: bool,
+    options: &DeleteOptions,
+) -> Result<Vec<DeleteResult>> {
+    if !resource.supports("delete") {
+        return Err(AppError::Invalid(format!(
+            "{} ({}) does not support delete",
+            resource.kind, resource.api_version
+        )));
+    }
+
+    let targets = resolve_targets(client, resource, namespace, names, label_selector, all_namespaces, "delete").await?;
+    let params = options.delete_params();
+
+    let results = join_all(targets.into_iter().map(|(namespace, name)| {
+        let api = dynamic_api(client, resource, namespace.as_deref());
+        let params = &params;
+        async move {
+            match api.delete(&name, params).await {
+                Ok(outcome) => DeleteResult {
+                    namespace,
+                    name,
+                    success: true,
+                    // The object is returned while it still exists, a Status once it's gone
+                    pending: outcome.is_left(),
+                    error: None,
+                },
+                Err(e) => DeleteResult {
+                    namespace,
+                    name,
+                    success: false,
+                    pending: false,
+                    error: Some(e.into()),
+                },
+            }
+        }
+    }))
+    .await;
+
+    Ok(results)
+}
+
+/// Scale any resource that serves the `/scale` subresource, including custom
+/// resources that declare it
+pub async fn scale_resource(
+    client: &Client,
+    resource: &ApiResourceInfo,
+    namespace: Option<&str>,
+    name: &str,
+    replicas: i32,
+) -> Result<ScaleInfo> {
+    if !resource.subresources.iter().any(|s| s == "scale") {
+        return Err(AppError::Invalid(format!(
+            "{} ({}) does not have a scale subresource",
+            resource.kind, resource.api_version
+        )));
+    }
+    if replicas < 0 {
+        return Err(AppError::Invalid("Replicas must not be negative".to_string()));
+    }
+    if resource.namespaced && namespace.is_none() {
+        return Err(AppError::Invalid(format!("{} is namespaced: a namespace is required", resource.kind)));
+    }
+
+    let patch = serde_json::json!({
+        "spec": {
+            "replicas": replicas
+        }
+    });
+    let scale = dynamic_api(client, resource, namespace)
+        .patch_scale(name, &PatchParams::default(), &Patch::Merge(&patch))
+        .await?;
+    Ok(scale.into())
+}
+
+/// Remove finalizers from an object, all of them when `finalizers` is empty.
+/// Guarded by the object's resourceVersion so a concurrent change isn't
+/// overwritten. Returns the finalizers left on the object.
+pub async fn remove_finalizers(
+    client: &Client,
+    resource: &ApiResourceInfo,
+    namespace: Option<&str>,
+    name: &str,
+    finalizers: &[String],
+) -> Result<Vec<String>> {
+    if resource.namespaced && namespace.is_none() {
+        return Err(AppError::Invalid(format!("{} is namespaced: a namespace is required", resource.kind)));
+    }
+
+    let api = dynamic_api(client, resource, namespace);
+    let obj = api.get(name).await?;
+    let current = obj.metadata.finalizers.unwrap_or_default();
+    if current.is_empty() {
+        return Err(AppError::Invalid(format!("{} {} has no finalizers", resource.kind, name)));
+    }
+    if let Some(unknown) = finalizers.iter().find(|f| !current.contains(f)) {
+        return Err(AppError::NotFound(format!(
+            "{} {} has no finalizer '{}' (finalizers: {})",
+            resource.kind,
+            name,
+            unknown,
+            current.join(", ")
+        )));
+    }
+
+    let remaining: Vec<String> = current
+        .into_iter()
+        .filter(|f| !finalizers.is_empty() && !finalizers.contains(f))
+        .collect();
+    let patch = serde_json::json!({
+        "metadata": {
+            "finalizers": remaining,
+            "resourceVersion": obj.metadata.resource_version,
+        }
+    });
+    let updated = api.patch(name, &PatchParams::default(), &Patch::Merge(&patch)).await?;
+    Ok(updated.metadata.finalizers.unwrap_or_default())
+}
+
+/// Add, update or remove labels or annotations on objects of any kind, by name or
+/// label selector. Failures are reported per object.
+#[allow(clippy::too_many_arguments)]
+pub async fn update_metadata(
+    client: &Client,
+    resource: &ApiResourceInfo,
+    namespace: Option<&str>,
+    names: &[String],
+    label_selector: Option<&str>,
+    all_namespaces: bool,
+    field: MetadataField,
+    changes: &MetadataChanges,
+) -> Result<Vec<MetadataResult>> {
+    if changes.set.is_empty() && changes.remove.is_empty() {
+        return Err(AppError::Invalid(format!("No {} to change", field.key())));
+    }
+    for (key, value) in &changes.set {
+        validate_qualified_name(key, &format!("{} key", field.singular()))?;
+        if let MetadataField::Labels = field {
+            validate_label_value(value, "Label value")?;
+        }
+        if changes.remove.contains(key) {
+            return Err(AppError::Invalid(format!("{} '{}' is both set and removed", field.singular(), key)));
+        }
+    }
+    for key in &changes.remove {
+        validate_qualified_name(key, &format!("{} key", field.singular()))?;
+    }
+    if !resource.supports("patch") {
+        return Err(AppError::Invalid(format!(
+            "{} ({}) does not support patch",
+            resource.kind, resource.api_version
+        )));
+    }
+
+    let targets = resolve_targets(client, resource, namespace, names, label_selector, all_namespaces, "update").await?;
+    let results = join_all(targets.into_iter().map(|(namespace, name)| {
+        let api = dynamic_api(client, resource, namespace.as_deref());
+        async move {
+            match update_object_metadata(&api, &name, field, changes).await {
+                Ok(values) => MetadataResult {
+                    namespace,
+                    name,
+                    success: true,
+                    values,
+                    error: None,
+                },
+                Err(e) => MetadataResult {
+                    namespace,
+                    name,
+                    success: false,
+                    values: BTreeMap::new(),
+                    error: Some(e),
+                },
+            }
+        }
+    }))
+    .await;
+
+    Ok(results)
+}
+
+async fn update_object_metadata(
+    api: &Api<DynamicObject>,
+    name: &str,
+    field: MetadataField,
+    changes: &MetadataChanges,
+) -> Result<BTreeMap<String, String>> {
+    let obj = api.get(name).await?;
+    let current = match field {
+        MetadataField::Labels => obj.metadata.labels.unwrap_or_default(),
+        MetadataField::Annotations => obj.metadata.annotations.unwrap_or_default(),
+    };
+
+    if !changes.overwrite {
+        if let Some((key, value)) = changes
+            .set
+            .iter()
+            .find(|(key, value)| current.get(*key).is_some_and(|existing| existing != *value))
+        {
+            return Err(AppError::Conflict(format!(
+                "{} '{}' is already set to '{}' (not '{}'): allow overwriting to replace it",
+                field.singular(),
+                key,
+                current[key],
+                value
+            )));
+        }
+    }
+
+    let mut updated = current.clone();
+    updated.extend(changes.set.clone());
+    for key in &changes.remove {
+        updated.remove(key);
+    }
+    if let MetadataField::Annotations = field {
+        let size: usize = updated.iter().map(|(k, v)| k.len() + v.len()).sum();
+        if size > MAX_ANNOTATIONS_SIZE {
+            return Err(AppError::Invalid(format!(
+                "Annotations would total {} bytes, over the 256 KiB limit",
+                size
+            )));
+        }
+    }
+
+    let mut values = serde_json::Map::new();
+    for (key, value) in &changes.set {
+        values.insert(key.clone(), serde_json::Value::String(value.clone()));
+    }
+    for key in changes.remove.iter().filter(|k| current.contains_key(*k)) {
+        values.insert(key.clone(), serde_json::Value::Null);
+    }
+    // Guard against a concurrent change to the values we checked
+    let patch = serde_json::json!({
+        "metadata": {
+            field.key(): values,
+            "resourceVersion": obj.metadata.resource_version,
+        }
+    });
+    let patched = api.patch(name, &PatchParams::default(), &Patch::Merge(&patch)).await?;
+    Ok(match field {
+        MetadataField::Labels => patched.metadata.labels.unwrap_or_default(),
+        MetadataField::Annotations => patched.metadata.annotations.unwrap_or_default(),
+    })
+}
+
+/// `(namespace, name)` of every object to act on; `verb` is used in error messages
+async fn resolve_targets(
+    client: &Client,
+    resource: &ApiResourceInfo,
+    namespace: Option<&str>,
+    names: &[String],
+    label_selector: Option<&str>,
+    all_namespaces: bool,
+    verb: &str,
+) -> Result<Vec<(Option<String>, String)>> {
+    let namespace = if resource.namespaced { namespace } else { None };
+
+    match label_selector {
+        Some(_) if !names.is_empty() => Err(AppError::Invalid(
+            "Specify either names or a label selector, not both".to_string(),
+        )),
+        Some(selector) => {
+            // An empty selector matches everything
+            let selector = selector.trim();
+            if selector.is_empty() {
+                return Err(AppError::Invalid("Label selector must not be empty".to_string()));
+            }
+            // Like `kubectl -l`: stay in one namespace unless all were asked for
+            let namespace = if all_namespaces { None } else { namespace };
+            if resource.namespaced && namespace.is_none() && !all_namespaces {
+                return Err(AppError::Invalid(format!(
+                    "{} is namespaced: give a namespace or select all namespaces to {} by label selector",
+                    resource.kind, verb
+                )));
+            }
+            let list = dynamic_api(client, resource, namespace)
+                .list_metadata(&ListParams::default().labels(selector))
+                .await?;
+            Ok(list
+                .items
+                .into_iter()
+                .filter_map(|obj| Some((obj.metadata.namespace, obj.metadata.name?)))
+                .collect())
+        }
+        None if names.is_empty() => Err(AppError::Invalid(format!(
+            "No objects to {}: give names or a label selector",
+            verb
+        ))),
+        None if resource.namespaced && namespace.is_none() => Err(AppError::Invalid(format!(
+            "{} is namespaced: a namespace is required to {} by name",
+            resource.kind, verb
+        ))),
+        None => Ok(names
+            .iter()
+            .map(|name| (namespace.map(str::to_string), name.clone()))
+            .collect()),
+    }
+}
@@ -0,0 +1,644 @@
+use k8s_openapi::api::apps::v1::{ControllerRevision, DaemonSet, Deployment, ReplicaSet, StatefulSet};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, ObjectMeta};
+use kube::api::{Api, ListParams, Patch, PatchParams, PostParams};
+use kube::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{AppError, Result};
+use crate::kubernetes::get_age;
+
+const REVISION_ANNOTATION: &str = "deployment.kubernetes.io/revision";
+const CHANGE_CAUSE_ANNOTATION: &str = "kubernetes.io/change-cause";
+const POD_TEMPLATE_HASH_LABEL: &str = "pod-template-hash";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum RolloutKind {
+    Deployment,
+    StatefulSet,
+    DaemonSet,
+}
+
+impl RolloutKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RolloutKind::Deployment => "Deployment",
+            RolloutKind::StatefulSet => "StatefulSet",
+            RolloutKind::DaemonSet => "DaemonSet",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RolloutRevision {
+    pub revision: i64,
+    /// ReplicaSet (Deployments) or ControllerRevision holding this revision
+    pub source: String,
+    pub change_cause: Option<String>,
+    pub images: Vec<String>,
+    pub created: Option<String>,
+    pub age: String,
+    /// The revision the workload is currently rolled out to
+    pub current: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffLineKind {
+    Unchanged,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub text: String,
+}
+
+/// Line diff of two revisions' pod templates, rendered as YAML
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevisionDiff {
+    pub from_revision: i64,
+    pub to_revision: i64,
+    pub changed: bool,
+    pub lines: Vec<DiffLine>,
+}
+
+struct Revision {
+    info: RolloutRevision,
+    /// Pod template, without controller-added labels
+    template: Value,
+    /// ControllerRevision data: a strategic merge patch restoring this revision
+    patch: Option<Value>,
+}
+
+pub async fn get_history(client: &Client, kind: RolloutKind, namespace: &str, name: &str) -> Result<Vec<RolloutRevision>> {
+    Ok(load_revisions(client, kind, namespace, name)
+        .await?
+        .into_iter()
+        .map(|r| r.info)
+        .collect())
+}
+
+pub async fn diff_revisions(
+    client: &Client,
+    kind: RolloutKind,
+    namespace: &str,
+    name: &str,
+    from_revision: i64,
+    to_revision: i64,
+) -> Result<RevisionDiff> {
+    let revisions = load_revisions(client, kind, namespace, name).await?;
+    let from = find_revision(&revisions, kind, name, from_revision)?;
+    let to = find_revision(&revisions, kind, name, to_revision)?;
+
+    let lines = diff_lines(
+        &serde_yaml::to_string(&from.template)?,
+        &serde_yaml::to_string(&to.template)?,
+    );
+    Ok(RevisionDiff {
+        from_revision,
+        to_revision,
+        changed: lines.iter().any(|l| l.kind != DiffLineKind::Unchanged),
+        lines,
+    })
+}
+
+/// Roll the workload's pod template back to an earlier revision. This creates a
+/// new revision, as `kubectl rollout undo` does.
+pub async fn rollback(client: &Client, kind: RolloutKind, namespace: &str, name: &str, revision: i64) -> Result<()> {
+    let revisions = load_revisions(client, kind, namespace, name).await?;
+    let target = find_revision(&revisions, kind, name, revision)?;
+    if target.info.current {
+        return Err(AppError::Invalid(format!(
+            "{} {} is already at revision {}",
+            kind.as_str(),
+            name,
+            revision
+        )));
+    }
+
+    match kind {
+        RolloutKind::Deployment => {
+            let deployments: Api<Deployment> = Api::namespaced(client.clone(), namespace);
+            let mut deploy = deployments.get(name).await?;
+            let spec = deploy
+                .spec
+                .as_mut()
+                .ok_or_else(|| AppError::Invalid(format!("Deployment {} has no spec", name)))?;
+            if spec.paused == Some(true) {
+                return Err(AppError::Invalid(format!(
+                    "Deployment {} is paused: resume it before rolling back",
+                    name
+                )));
+            }
+            spec.template = serde_json::from_value(target.template.clone())?;
+
+            let annotations = deploy.metadata.annotations.get_or_insert_with(Default::default);
+            match &target.info.change_cause {
+                Some(cause) => annotations.insert(CHANGE_CAUSE_ANNOTATION.to_string(), cause.clone()),
+                None => annotations.remove(CHANGE_CAUSE_ANNOTATION),
+            };
+
+            // Replace rather than patch so fields removed since that revision are
+            // removed again; the resourceVersion guards against concurrent edits
+            deployments.replace(name, &PostParams::default(), &deploy).await?;
+        }
+        RolloutKind::StatefulSet | RolloutKind::DaemonSet => {
+            let patch = target
+                .patch
+                .as_ref()
+                .ok_or_else(|| AppError::Invalid(format!("Revision {} has no data", revision)))?;
+            let params = PatchParams::default();
+            if let RolloutKind::StatefulSet = kind {
+                let api: Api<StatefulSet> = Api::namespaced(client.clone(), namespace);
+                api.patch(name, &params, &Patch::Strategic(patch)).await?;
+            } else {
+                let api: Api<DaemonSet> = Api::namespaced(client.clone(), namespace);
+                api.patch(name, &params, &Patch::Strategic(patch)).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn find_revision<'a>(revisions: &'a [Revision], kind: RolloutKind, name: &str, revision: i64) -> Result<&'a Revision> {
+    revisions
+        .iter()
+        .find(|r| r.info.revision == revision)
+        .ok_or_else(|| AppError::NotFound(format!("{} {} has no revision {}", kind.as_str(), name, revision)))
+}
+
+/// Revisions of a workload, oldest first
+async fn load_revisions(client: &Client, kind: RolloutKind, namespace: &str, name: &str) -> Result<Vec<Revision>> {
+    let mut revisions = match kind {
+        RolloutKind::Deployment => deployment_revisions(client, namespace, name).await?,
+        RolloutKind::StatefulSet => {
+            let sts = Api::<StatefulSet>::namespaced(client.clone(), namespace).get(name).await?;
+            let update_revision = sts.status.as_ref().and_then(|s| s.update_revision.clone());
+            let selector = sts.spec.as_ref().map(|s| &s.selector);
+            controller_revisions(client, namespace, &sts.metadata, selector, update_revision).await?
+        }
+        RolloutKind::DaemonSet => {
+            let ds = Api::<DaemonSet>::namespaced(client.clone(), namespace).get(name).await?;
+            let selector = ds.spec.as_ref().map(|s| &s.selector);
+            controller_revisions(client, namespace, &ds.metadata, selector, None).await?
+        }
+    };
+    revisions.sort_by_key(|r| r.info.revision);
+
+    // DaemonSets don't report their current revision: it is always the newest
+    if let (RolloutKind::DaemonSet, Some(last)) = (kind, revisions.last_mut()) {
+        last.info.current = true;
+    }
+    Ok(revisions)
+}
+
+async fn deployment_revisions(client: &Client, namespace: &str, name: &str) -> Result<Vec<Revision>> {
+    let deploy = Api::<Deployment>::namespaced(client.clone(), namespace).get(name).await?;
+    let current = annotation(&deploy.metadata, REVISION_ANNOTATION);
+    let selector = deploy.spec.as_ref().map(|s| &s.selector);
+
+    let replica_sets: Api<ReplicaSet> = Api::namespaced(client.clone(), namespace);
+    let list = replica_sets.list(&list_params(selector)).await?;
+
+    let mut revisions = Vec::new();
+    for rs in list.items {
+        if !owned_by(&rs.metadata, &deploy.metadata) {
+            continue;
+        }
+        let Some(revision_str) = annotation(&rs.metadata, REVISION_ANNOTATION) else {
+            continue;
+        };
+        let Ok(revision) = revision_str.parse::<i64>() else {
+            continue;
+        };
+
+        let mut template = serde_json::to_value(rs.spec.as_ref().and_then(|s| s.template.as_ref()))?;
+        if let Some(labels) = template.pointer_mut("/metadata/labels").and_then(Value::as_object_mut) {
+            labels.remove(POD_TEMPLATE_HASH_LABEL);
+        }
+
+        revisions.push(Revision {
+            info: RolloutRevision {
+                revision,
+                source: rs.metadata.name.clone().unwrap_or_default(),
+                change_cause: annotation(&rs.metadata, CHANGE_CAUSE_ANNOTATION),
+                images: template_images(&template),
+                created: rs.metadata.creation_timestamp.as_ref().map(|t| t.0.to_rfc3339()),
+                age: get_age(rs.metadata.creation_timestamp.as_ref()),
+                current: current.as_deref() == Some(revision_str.as_str()),
+            },
+            template,
+            patch: None,
+        });
+    }
+    Ok(revisions)
+}
+
+async fn controller_revisions(
+    client: &Client,
+    namespace: &str,
+    owner: &ObjectMeta,
+    selector: Option<&LabelSelector>,
+    current_name: Option<String>,
+) -> Result<Vec<Revision>> {
+    let api: Api<ControllerRevision> = Api::namespaced(client.clone(), namespace);
+    let list = api.list(&list_params(selector)).await?;
+
+    Ok(list
+        .items
+        .into_iter()
+        .filter(|cr| owned_by(&cr.metadata, owner))
+        .map(|cr| {
+            let patch = cr.data.map(|d| d.0);
+            let mut template = patch
+                .as_ref()
+                .and_then(|p| p.pointer("/spec/template"))
+                .cloned()
+                .unwrap_or(Value::Null);
+            if let Some(obj) = template.as_object_mut() {
+                obj.remove("$patch");
+            }
+            let name = cr.metadata.name.clone().unwrap_or_default();
+
+            Revision {
+                info: RolloutRevision {
+                    revision: cr.revision,
+                    current: current_name.as_deref() == Some(name.as_str()),
+                    source: name,
+                    change_cause: annotation(&cr.metadata, CHANGE_CAUSE_ANNOTATION),
+                    images: template_images(&template),
+                    created: cr.metadata.creation_timestamp.as_ref().map(|t| t.0.to_rfc3339()),
+                    age: get_age(cr.metadata.creation_timestamp.as_ref()),
+                },
+                template,
+                patch,
+            }
+        })
+        .collect())
+}
+
+/// Narrow the list by the workload's matchLabels; ownership is checked afterwards
+fn list_params(selector: Option<&LabelSelector>) -> ListParams {
+    let labels = selector
+        .and_then(|s| s.match_labels.as_ref())
+        .map(|labels| {
+            labels
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .unwrap_or_default();
+    if labels.is_empty() {
+        ListParams::default()
+    } else {
+        ListParams::default().labels(&labels)
+    }
+}
+
+fn owned_by(meta: &ObjectMeta, owner: &ObjectMeta) -> bool {
+    let Some(uid) = owner.uid.as_deref() else {
+        return false;
+    };
+    meta.owner_references
+        .iter()
+        .flatten()
+        .any(|r| r.uid == uid)
+}
+
+fn annotation(meta: &ObjectMeta, key: &str) -> Option<String> {
+    meta.annotations.as_ref()?.get(key).cloned()
+}
+
+fn template_images(template: &Value) -> Vec<String> {
+    template
+        .pointer("/spec/containers")
+        .and_then(Value::as_array)
+        .map(|containers| {
+            containers
+                .iter()
+                .filter_map(|c| c.get("image").and_then(Value::as_str))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Largest LCS table (in cells) diff_lines will build
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Line diff via longest common subsequence. The common prefix and suffix are
+/// trimmed first, so the quadratic table only covers the changed region; if
+/// that is still over `MAX_DIFF_CELLS`, the region is shown as removed then
+/// added rather than aligned line by line.
+pub(crate) fn diff_lines(from: &str, to: &str) -> Vec<DiffLine> {
+    let a: Vec<&str> = from.lines().collect();
+    let b: Vec<&str> = to.lines().collect();
+
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (a_mid, b_mid) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+
+    let line = |kind, text: &str| DiffLine {
+        kind,
+        text: text.to_string(),
+    };
+    let mut lines: Vec<DiffLine> = a[..prefix].iter().map(|l| line(DiffLineKind::Unchanged, l)).collect();
+
+    if (a_mid.len() + 1).saturating_mul(b_mid.len() + 1) > MAX_DIFF_CELLS {
+        lines.extend(a_mid.iter().map(|l| line(DiffLineKind::Removed, l)));
+        lines.extend(b_mid.iter().map(|l| line(DiffLineKind::Added, l)));
+    } else {
+        // lcs[i][j] = length of the LCS of a_mid[i..] and b_mid[j..]
+        let mut lcs = vec![vec![0u32; b_mid.len() + 1]; a_mid.len() + 1];
+        for i in (0..a_mid.len()).rev() {
+            for j in (0..b_mid.len()).rev() {
+                lcs[i][j] = if a_mid[i] == b_mid[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < a_mid.len() && j < b_mid.len() {
+            if a_mid[i] == b_mid[j] {
+                lines.push(line(DiffLineKind::Unchanged, a_mid[i]));
+                i += 1;
+                j += 1;
+            } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+                lines.push(line(DiffLineKind::Removed, a_mid[i]));
+                i += 1;
+            } else {
+                lines.push(line(DiffLineKind::Added, b_mid[j]));
+                j += 1;
+            }
+        }
+        lines.extend(a_mid[i..].iter().map(|l| line(DiffLineKind::Removed, l)));
+        lines.extend(b_mid[j..].iter().map(|l| line(DiffLineKind::Added, l)));
+    }
+
+    lines.extend(a[a.len() - suffix..].iter().map(|l| line(DiffLineKind::Unchanged, l)));
+    lines
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RolloutPhase {
+    Progressing,
+    Complete,
+    /// The workload was deleted while being watched
+    Failed,
+    /// `progressDeadlineSeconds` was exceeded, or the watch timed out
+    TimedOut,
+    /// The update strategy (`OnDelete`) has no rollout to follow
+    Unsupported,
+}
+
+impl RolloutPhase {
+    pub fn is_finished(&self) -> bool {
+        *self != RolloutPhase::Progressing
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RolloutStatus {
+    pub kind: RolloutKind,
+    pub namespace: String,
+    pub name: String,
+    pub phase: RolloutPhase,
+    pub message: String,
+    pub generation: Option<i64>,
+    pub observed_generation: Option<i64>,
+    pub desired: i32,
+    pub updated: i32,
+    pub ready: i32,
+    pub available: i32,
+    pub timestamp: String,
+}
+
+/// Returned by `watch_rollout_status`: the status when the watch started and,
+/// while the rollout is still progressing, the id to follow it with
+#[derive(Debug, Clone, Serialize)]
+pub struct RolloutWatch {
+    pub watch_id: Option<String>,
+    pub status: RolloutStatus,
+}
+
+impl RolloutStatus {
+    pub fn new(kind: RolloutKind, meta: &ObjectMeta) -> Self {
+        Self {
+            kind,
+            namespace: meta.namespace.clone().unwrap_or_default(),
+            name: meta.name.clone().unwrap_or_default(),
+            phase: RolloutPhase::Progressing,
+            message: String::new(),
+            generation: meta.generation,
+            observed_generation: None,
+            desired: 0,
+            updated: 0,
+            ready: 0,
+            available: 0,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    pub fn finish(mut self, phase: RolloutPhase, message: impl Into<String>) -> Self {
+        self.phase = phase;
+        self.message = message.into();
+        self.timestamp = chrono::Utc::now().to_rfc3339();
+        self
+    }
+
+    fn progressing(self, message: impl Into<String>) -> Self {
+        self.finish(RolloutPhase::Progressing, message)
+    }
+
+    fn generation_observed(&self) -> bool {
+        match (self.generation, self.observed_generation) {
+            (Some(generation), Some(observed)) => observed >= generation,
+            _ => false,
+        }
+    }
+}
+
+/// Rollout progress, following the same rules as `kubectl rollout status`
+pub fn deployment_status(deploy: &Deployment) -> RolloutStatus {
+    let mut status = RolloutStatus::new(RolloutKind::Deployment, &deploy.metadata);
+    let name = status.name.clone();
+    let Some(s) = deploy.status.as_ref() else {
+        return status.progressing("Waiting for deployment status");
+    };
+    status.observed_generation = s.observed_generation;
+    status.desired = deploy.spec.as_ref().and_then(|s| s.replicas).unwrap_or(1);
+    status.updated = s.updated_replicas.unwrap_or(0);
+    status.ready = s.ready_replicas.unwrap_or(0);
+    status.available = s.available_replicas.unwrap_or(0);
+    let replicas = s.replicas.unwrap_or(0);
+
+    if !status.generation_observed() {
+        return status.progressing("Waiting for deployment spec update to be observed");
+    }
+    let progressing = s
+        .conditions
+        .iter()
+        .flatten()
+        .find(|c| c.type_ == "Progressing");
+    if let Some(condition) = progressing.filter(|c| c.reason.as_deref() == Some("ProgressDeadlineExceeded")) {
+        let message = condition.message.clone().unwrap_or_default();
+        return status.finish(
+            RolloutPhase::TimedOut,
+            format!("Deployment {} exceeded its progress deadline: {}", name, message),
+        );
+    }
+
+    let (desired, updated, available) = (status.desired, status.updated, status.available);
+    if updated < desired {
+        status.progressing(format!("{} of {} new replicas have been updated", updated, desired))
+    } else if replicas > updated {
+        status.progressing(format!("{} old replicas are pending termination", replicas - updated))
+    } else if available < updated {
+        status.progressing(format!("{} of {} updated replicas are available", available, updated))
+    } else {
+        status.finish(RolloutPhase::Complete, format!("Deployment {} successfully rolled out", name))
+    }
+}
+
+pub fn statefulset_status(sts: &StatefulSet) -> RolloutStatus {
+    let mut status = RolloutStatus::new(RolloutKind::StatefulSet, &sts.metadata);
+    let name = status.name.clone();
+    let spec = sts.spec.as_ref();
+    let strategy = spec.and_then(|s| s.update_strategy.as_ref());
+    if strategy.and_then(|s| s.type_.as_deref()) == Some("OnDelete") {
+        return status.finish(
+            RolloutPhase::Unsupported,
+            "Rollout status is only available for the RollingUpdate strategy",
+        );
+    }
+    let Some(s) = sts.status.as_ref() else {
+        return status.progressing("Waiting for statefulset status");
+    };
+    status.observed_generation = s.observed_generation;
+    status.desired = spec.and_then(|s| s.replicas).unwrap_or(1);
+    status.updated = s.updated_replicas.unwrap_or(0);
+    status.ready = s.ready_replicas.unwrap_or(0);
+    status.available = s.available_replicas.unwrap_or(0);
+
+    if !status.generation_observed() {
+        return status.progressing("Waiting for statefulset spec update to be observed");
+    }
+    let (desired, updated, ready) = (status.desired, status.updated, status.ready);
+    if ready < desired {
+        return status.progressing(format!("Waiting for {} pods to be ready", desired - ready));
+    }
+
+    let partition = strategy
+        .and_then(|s| s.rolling_update.as_ref())
+        .and_then(|r| r.partition)
+        .unwrap_or(0);
+    if partition > 0 {
+        let expected = (desired - partition).max(0);
+        return if updated < expected {
+            status.progressing(format!(
+                "Waiting for partitioned roll out to finish: {} out of {} new pods have been updated",
+                updated, expected
+            ))
+        } else {
+            status.finish(
+                RolloutPhase::Complete,
+                format!("Partitioned roll out complete: {} new pods have been updated", updated),
+            )
+        };
+    }
+
+    if s.update_revision != s.current_revision {
+        return status.progressing(format!(
+            "Waiting for rolling update to complete: {} pods at revision {}",
+            updated,
+            s.update_revision.as_deref().unwrap_or_default()
+        ));
+    }
+    status.finish(
+        RolloutPhase::Complete,
+        format!("StatefulSet {} rolling update complete: {} pods", name, ready),
+    )
+}
+
+pub fn daemonset_status(ds: &DaemonSet) -> RolloutStatus {
+    let mut status = RolloutStatus::new(RolloutKind::DaemonSet, &ds.metadata);
+    let name = status.name.clone();
+    let strategy = ds.spec.as_ref().and_then(|s| s.update_strategy.as_ref());
+    if strategy.and_then(|s| s.type_.as_deref()) == Some("OnDelete") {
+        return status.finish(
+            RolloutPhase::Unsupported,
+            "Rollout status is only available for the RollingUpdate strategy",
+        );
+    }
+    let Some(s) = ds.status.as_ref() else {
+        return status.progressing("Waiting for daemon set status");
+    };
+    status.observed_generation = s.observed_generation;
+    status.desired = s.desired_number_scheduled;
+    status.updated = s.updated_number_scheduled.unwrap_or(0);
+    status.ready = s.number_ready;
+    status.available = s.number_available.unwrap_or(0);
+
+    if !status.generation_observed() {
+        return status.progressing("Waiting for daemon set spec update to be observed");
+    }
+    let (desired, updated, available) = (status.desired, status.updated, status.available);
+    if updated < desired {
+        status.progressing(format!("{} out of {} new pods have been updated", updated, desired))
+    } else if available < desired {
+        status.progressing(format!("{} of {} updated pods are available", available, desired))
+    } else {
+        status.finish(RolloutPhase::Complete, format!("DaemonSet {} successfully rolled out", name))
+    }
+}
+
+/// The rollout's status right now, by the same rules as the watch
+pub async fn current_status(client: &Client, kind: RolloutKind, namespace: &str, name: &str) -> Result<RolloutStatus> {
+    Ok(match kind {
+        RolloutKind::Deployment => {
+            deployment_status(&Api::<Deployment>::namespaced(client.clone(), namespace).get(name).await?)
+        }
+        RolloutKind::StatefulSet => {
+            statefulset_status(&Api::<StatefulSet>::namespaced(client.clone(), namespace).get(name).await?)
+        }
+        RolloutKind::DaemonSet => {
+            daemonset_status(&Api::<DaemonSet>::namespaced(client.clone(), namespace).get(name).await?)
+        }
+    })
+}
+
+/// Poll until the rollout finishes or `timeout` passes, for commands that wait
+/// inline instead of following progress events
+pub async fn wait_for_rollout(
+    client: &Client,
+    kind: RolloutKind,
+    namespace: &str,
+    name: &str,
+    timeout: std::time::Duration,
+) -> Result<RolloutStatus> {
+    let started = std::time::Instant::now();
+    loop {
+        let status = current_status(client, kind, namespace, name).await?;
+        if status.phase.is_finished() {
+            return Ok(status);
+        }
+        if started.elapsed() >= timeout {
+            let message = format!("Timed out after {}s: {}", timeout.as_secs(), status.message);
+            return Ok(status.finish(RolloutPhase::TimedOut, message));
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::error::{AppError, Result};
+
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Text to type to turn read-only mode off
+pub const READ_WRITE_CONFIRMATION: &str = "read-write";
+
+/// Ordered from least to most restrictive
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtectionLevel {
+    /// Mutations run without extra checks
+    #[default]
+    None,
+    /// Mutations require the context name to be typed as confirmation
+    Protected,
+    /// All mutations are rejected
+    ReadOnly,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContextSettings {
+    /// CSS colour used to tag the context in the UI
+    pub color: Option<String>,
+    /// Short label shown next to the context name, e.g. "PROD"
+    pub label: Option<String>,
+    pub protection: ProtectionLevel,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    /// Reject every mutating command, whatever the context
+    pub read_only: bool,
+    pub contexts: HashMap<String, ContextSettings>,
+}
+
+/// App settings persisted as JSON in the app data dir
+pub struct SettingsManager {
+    path: PathBuf,
+    settings: RwLock<AppSettings>,
+}
+
+impl SettingsManager {
+    /// Load settings from `data_dir`, falling back to defaults if the file is
+    /// missing or unreadable
+    pub fn load(data_dir: PathBuf) -> Self {
+        let path = data_dir.join(SETTINGS_FILE);
+        let settings = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring invalid settings file {:?}: {}", path, e);
+                AppSettings::default()
+            }),
+            Err(_) => AppSettings::default(),
+        };
+
+        Self {
+            path,
+            settings: RwLock::new(settings),
+        }
+    }
+
+    pub async fn settings(&self) -> AppSettings {
+        self.settings.read().await.clone()
+    }
+
+    pub async fn context_settings(&self, context_name: &str) -> ContextSettings {
+        self.settings
+            .read()
+            .await
+            .contexts
+            .get(context_name)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Turning read-only mode off needs `confirmation` to be `READ_WRITE_CONFIRMATION`
+    pub async fn set_read_only(&self, read_only: bool, confirmation: Option<&str>) -> Result<()> {
+        let mut settings = self.settings.write().await;
+        if settings.read_only && !read_only && confirmation != Some(READ_WRITE_CONFIRMATION) {
+            return Err(AppError::Invalid(format!(
+                "Type '{}' to turn off read-only mode",
+                READ_WRITE_CONFIRMATION
+            )));
+        }
+        settings.read_only = read_only;
+        self.save(&settings)
+    }
+
+    /// Reject the command if app-wide read-only mode is on
+    pub async fn ensure_not_read_only(&self) -> Result<()> {
+        if self.settings.read().await.read_only {
+            return Err(AppError::ReadOnly(
+                "Read-only mode is enabled: changes to the cluster are disabled".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Lowering a context's protection level needs `confirmation` to be the
+    /// context name
+    pub async fn set_context_settings(
+        &self,
+        context_name: &str,
+        context_settings: ContextSettings,
+        confirmation: Option<&str>,
+    ) -> Result<()> {
+        let mut settings = self.settings.write().await;
+        let current = settings
+            .contexts
+            .get(context_name)
+            .map(|c| c.protection)
+            .unwrap_or_default();
+        if context_settings.protection < current && confirmation != Some(context_name) {
+            return Err(AppError::ConfirmationRequired(context_name.to_string()));
+        }
+        settings.contexts.insert(context_name.to_string(), context_settings);
+        self.save(&settings)
+    }
+
+    /// Carry a context's settings over when it is renamed in kubeconfig
+    pub async fn rename_context(&self, old_name: &str, new_name: &str) -> Result<()> {
+        let mut settings = self.settings.write().await;
+        if let Some(context_settings) = settings.contexts.remove(old_name) {
+            settings.contexts.insert(new_name.to_string(), context_settings);
+            self.save(&settings)?;
+        }
+        Ok(())
+    }
+
+    pub async fn remove_context(&self, context_name: &str) -> Result<()> {
+        let mut settings = self.settings.write().await;
+        if settings.contexts.remove(context_name).is_some() {
+            self.save(&settings)?;
+        }
+        Ok(())
+    }
+
+    /// Reject a mutating command if read-only mode is on or the context's
+    /// protection level doesn't allow it. Protected contexts need `confirmation`
+    /// to be the context name.
+    pub async fn ensure_mutation_allowed(&self, context_name: &str, confirmation: Option<&str>) -> Result<()> {
+        self.ensure_not_read_only().await?;
+        match self.context_settings(context_name).await.protection {
+            ProtectionLevel::None => Ok(()),
+            ProtectionLevel::Protected if confirmation == Some(context_name) => Ok(()),
+            ProtectionLevel::Protected => Err(AppError::ConfirmationRequired(context_name.to_string())),
+            ProtectionLevel::ReadOnly => Err(AppError::ReadOnly(format!(
+                "Context '{}' is read-only",
+                context_name
+            ))),
+        }
+    }
+
+    fn save(&self, settings: &AppSettings) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(settings)?;
+        // Write to a temp file first so a crash can't leave a truncated settings file
+        let tmp_path = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, json)?;
+        std::fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
@@ -2,18 +2,21 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
-use kube::Api;
+use kube::{Api, Client};
 use kube::runtime::watcher::{self, Event};
-use k8s_openapi::api::apps::v1::Deployment;
+use k8s_openapi::api::apps::v1::{DaemonSet, Deployment, StatefulSet};
 use k8s_openapi::api::batch::v1::Job;
 use k8s_openapi::api::core::v1::{Event as K8sEvent, Node, Pod};
 use futures::TryStreamExt;
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
 use uuid::Uuid;
 
+use crate::audit::{object_name, AuditAction, AuditLog, AuditOutcome};
+use crate::drain::{self, DrainOptions, DrainPlan, DrainPod, DrainPodPhase, DrainProgress, DrainStatus};
 use crate::error::Result;
 use crate::kubernetes::{self, ClusterEventInfo, DeploymentInfo, JobInfo, NodeInfo, PodInfo};
+use crate::rollout::{self, RolloutKind, RolloutPhase, RolloutStatus};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PodWatchEvent {
@@ -52,10 +55,21 @@ pub struct ClusterEventWatchEvent {
 
 struct WatchHandle {
     shutdown_tx: tokio::sync::oneshot::Sender<()>,
+    /// Set for watches that hold their events until the frontend is listening
+    ready_tx: Option<tokio::sync::oneshot::Sender<()>>,
     #[allow(dead_code)]
     task: JoinHandle<()>,
 }
 
+/// How long a watch waits for `listener_ready` before emitting anyway
+const LISTENER_WAIT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Hold back a watch's first event until the frontend has attached its listener,
+/// so a short-lived watch can't finish before anyone hears it
+async fn wait_for_listener(ready_rx: tokio::sync::oneshot::Receiver<()>) {
+    let _ = tokio::time::timeout(LISTENER_WAIT, ready_rx).await;
+}
+
 pub struct WatchManager {
     watchers: Arc<RwLock<HashMap<String, WatchHandle>>>,
 }
@@ -70,10 +84,10 @@ impl WatchManager {
     pub async fn start_pod_watch(
         &self,
         app: AppHandle,
+        client: Client,
         namespace: Option<String>,
     ) -> Result<String> {
         let watch_id = Uuid::new_v4().to_string();
-        let client = kubernetes::create_client().await?;
 
         let pods: Api<Pod> = match &namespace {
             Some(ns) => Api::namespaced(client, ns),
@@ -157,6 +171,7 @@ impl WatchManager {
 
         self.watchers.write().await.insert(watch_id.clone(), WatchHandle {
             shutdown_tx,
+            ready_tx: None,
             task,
         });
 
@@ -167,10 +182,10 @@ impl WatchManager {
     pub async fn start_deployment_watch(
         &self,
         app: AppHandle,
+        client: Client,
         namespace: Option<String>,
     ) -> Result<String> {
         let watch_id = Uuid::new_v4().to_string();
-        let client = kubernetes::create_client().await?;
 
         let deployments: Api<Deployment> = match &namespace {
             Some(ns) => Api::namespaced(client, ns),
@@ -225,7 +240,7 @@ impl WatchManager {
             }
         });
 
-        self.watchers.write().await.insert(watch_id.clone(), WatchHandle { shutdown_tx, task });
+        self.watchers.write().await.insert(watch_id.clone(), WatchHandle { shutdown_tx, ready_tx: None, task });
         tracing::info!("Started deployment watch {} for namespace {:?}", watch_id, namespace);
         Ok(watch_id)
     }
@@ -233,10 +248,10 @@ impl WatchManager {
     pub async fn start_job_watch(
         &self,
         app: AppHandle,
+        client: Client,
         namespace: Option<String>,
     ) -> Result<String> {
         let watch_id = Uuid::new_v4().to_string();
-        let client = kubernetes::create_client().await?;
 
         let jobs: Api<Job> = match &namespace {
             Some(ns) => Api::namespaced(client, ns),
@@ -291,7 +306,7 @@ impl WatchManager {
             }
         });
 
-        self.watchers.write().await.insert(watch_id.clone(), WatchHandle { shutdown_tx, task });
+        self.watchers.write().await.insert(watch_id.clone(), WatchHandle { shutdown_tx, ready_tx: None, task });
         tracing::info!("Started job watch {} for namespace {:?}", watch_id, namespace);
         Ok(watch_id)
     }
@@ -299,9 +314,9 @@ impl WatchManager {
     pub async fn start_node_watch(
         &self,
         app: AppHandle,
+        client: Client,
     ) -> Result<String> {
         let watch_id = Uuid::new_v4().to_string();
-        let client = kubernetes::create_client().await?;
 
         // Nodes are cluster-scoped
         let nodes: Api<Node> = Api::all(client);
@@ -354,7 +369,7 @@ impl WatchManager {
             }
         });
 
-        self.watchers.write().await.insert(watch_id.clone(), WatchHandle { shutdown_tx, task });
+        self.watchers.write().await.insert(watch_id.clone(), WatchHandle { shutdown_tx, ready_tx: None, task });
         tracing::info!("Started node watch {}", watch_id);
         Ok(watch_id)
     }
@@ -362,10 +377,10 @@ impl WatchManager {
     pub async fn start_event_watch(
         &self,
         app: AppHandle,
+        client: Client,
         namespace: Option<String>,
     ) -> Result<String> {
         let watch_id = Uuid::new_v4().to_string();
-        let client = kubernetes::create_client().await?;
 
         let events: Api<K8sEvent> = match &namespace {
             Some(ns) => Api::namespaced(client, ns),
@@ -418,11 +433,180 @@ impl WatchManager {
             }
         });
 
-        self.watchers.write().await.insert(watch_id.clone(), WatchHandle { shutdown_tx, task });
+        self.watchers.write().await.insert(watch_id.clone(), WatchHandle { shutdown_tx, ready_tx: None, task });
         tracing::info!("Started event watch {} for namespace {:?}", watch_id, namespace);
         Ok(watch_id)
     }
 
+    /// Follow one workload's rollout, emitting `rollout-status-<id>` events until it
+    /// completes, fails or times out. Nothing is emitted until `listener_ready` is
+    /// called for the id. The watch removes itself once finished.
+    pub async fn start_rollout_watch(
+        &self,
+        app: AppHandle,
+        client: Client,
+        initial: RolloutStatus,
+        timeout: std::time::Duration,
+    ) -> Result<String> {
+        let watch_id = Uuid::new_v4().to_string();
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
+        let (ready_tx, ready_rx) = tokio::sync::oneshot::channel();
+        let (kind, namespace, name) = (initial.kind, initial.namespace.clone(), initial.name.clone());
+
+        // Hold the lock until the handle is in the map, so the task's own removal
+        // can't run first
+        let mut watchers = self.watchers.write().await;
+        let watchers_clone = self.watchers.clone();
+        let watch_id_clone = watch_id.clone();
+        let task = tokio::spawn(async move {
+            wait_for_listener(ready_rx).await;
+            let follow = RolloutFollow {
+                app,
+                event_name: format!("rollout-status-{}", watch_id_clone),
+                name: initial.name.clone(),
+                timeout,
+            };
+            let namespace = initial.namespace.clone();
+            match initial.kind {
+                RolloutKind::Deployment => {
+                    let api: Api<Deployment> = Api::namespaced(client, &namespace);
+                    follow.run(api, rollout::deployment_status, initial, shutdown_rx).await
+                }
+                RolloutKind::StatefulSet => {
+                    let api: Api<StatefulSet> = Api::namespaced(client, &namespace);
+                    follow.run(api, rollout::statefulset_status, initial, shutdown_rx).await
+                }
+                RolloutKind::DaemonSet => {
+                    let api: Api<DaemonSet> = Api::namespaced(client, &namespace);
+                    follow.run(api, rollout::daemonset_status, initial, shutdown_rx).await
+                }
+            }
+            watchers_clone.write().await.remove(&watch_id_clone);
+        });
+
+        watchers.insert(watch_id.clone(), WatchHandle { shutdown_tx, ready_tx: Some(ready_tx), task });
+        tracing::info!("Started rollout watch {} for {} {}/{}", watch_id, kind.as_str(), namespace, name);
+        Ok(watch_id)
+    }
+
+    /// Evict the pods of a prepared drain concurrently, emitting `node-drain-<id>`
+    /// per pod update. Nothing happens until `listener_ready` is called for the id.
+    /// Stopping the watch cancels the drain. `action` is audited against
+    /// `context_name` once the drain ends, with the pods it evicted.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn start_drain(
+        &self,
+        app: AppHandle,
+        client: Client,
+        context_name: String,
+        action: AuditAction,
+        plan: DrainPlan,
+        options: DrainOptions,
+    ) -> Result<String> {
+        let watch_id = Uuid::new_v4().to_string();
+        let (shutdown_tx, mut shutdown_rx) = tokio::sync::oneshot::channel();
+        let (ready_tx, ready_rx) = tokio::sync::oneshot::channel();
+
+        // Hold the lock until the handle is in the map, so the task's own removal
+        // can't run first
+        let mut watchers = self.watchers.write().await;
+        let watchers_clone = self.watchers.clone();
+        let watch_id_clone = watch_id.clone();
+        let node = plan.node.clone();
+        let task = tokio::spawn(async move {
+            wait_for_listener(ready_rx).await;
+            let event_name = format!("node-drain-{}", watch_id_clone);
+            let pods = std::sync::Mutex::new(
+                plan.evict.iter().chain(&plan.skipped).cloned().collect::<Vec<DrainPod>>(),
+            );
+            let emit = |status: DrainStatus, pod: Option<DrainPod>, message: Option<String>| {
+                let mut pods = pods.lock().unwrap_or_else(|e| e.into_inner());
+                if let Some(update) = &pod {
+                    if let Some(entry) = pods
+                        .iter_mut()
+                        .find(|p| p.namespace == update.namespace && p.name == update.name)
+                    {
+                        *entry = update.clone();
+                    }
+                }
+                let progress = DrainProgress {
+                    node: plan.node.clone(),
+                    status,
+                    pod,
+                    // Only the final event carries the full list
+                    pods: if status == DrainStatus::Running { Vec::new() } else { pods.clone() },
+                    message,
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                };
+                let _ = app.emit(&event_name, &progress);
+            };
+
+            let evictions = futures::future::join_all(plan.pods.iter().map(|pod| {
+                drain::evict_pod(&client, pod, options.grace_period_seconds, |update| {
+                    emit(DrainStatus::Running, Some(update), None)
+                })
+            }));
+
+            let (status, message) = tokio::select! {
+                _ = &mut shutdown_rx => (DrainStatus::Cancelled, Some("Drain cancelled; the node stays cordoned".to_string())),
+                _ = tokio::time::sleep(options.timeout()) => (
+                    DrainStatus::TimedOut,
+                    Some(format!("Drain did not finish within {}s; the node stays cordoned", options.timeout().as_secs())),
+                ),
+                _ = evictions => {
+                    let failed = pods
+                        .lock()
+                        .map(|p| p.iter().filter(|p| p.phase == DrainPodPhase::Failed).count())
+                        .unwrap_or(0);
+                    if failed > 0 {
+                        (DrainStatus::Failed, Some(format!("{} pod(s) could not be evicted", failed)))
+                    } else {
+                        (DrainStatus::Complete, None)
+                    }
+                }
+            };
+            emit(status, None, message.clone());
+
+            let outcome = {
+                let pods = pods.lock().unwrap_or_else(|e| e.into_inner());
+                let names = |phase: DrainPodPhase| -> Vec<String> {
+                    pods.iter()
+                        .filter(|p| p.phase == phase)
+                        .map(|p| object_name(Some(&p.namespace), &p.name))
+                        .collect()
+                };
+                let failed = names(DrainPodPhase::Failed);
+                AuditOutcome {
+                    success: status == DrainStatus::Complete,
+                    error: message.map(|m| {
+                        if failed.is_empty() { m } else { format!("{}: {}", m, failed.join(", ")) }
+                    }),
+                    objects: names(DrainPodPhase::Evicted),
+                }
+            };
+            app.state::<AuditLog>().record(&context_name, action, outcome).await;
+            watchers_clone.write().await.remove(&watch_id_clone);
+        });
+
+        watchers.insert(watch_id.clone(), WatchHandle { shutdown_tx, ready_tx: Some(ready_tx), task });
+        tracing::info!("Started drain {} for node {}", watch_id, node);
+        Ok(watch_id)
+    }
+
+    /// The frontend is listening for the watch's events; let it start emitting
+    pub async fn listener_ready(&self, watch_id: &str) -> Result<()> {
+        let ready_tx = self
+            .watchers
+            .write()
+            .await
+            .get_mut(watch_id)
+            .and_then(|handle| handle.ready_tx.take());
+        if let Some(ready_tx) = ready_tx {
+            let _ = ready_tx.send(());
+        }
+        Ok(())
+    }
+
     pub async fn stop_watch(&self, watch_id: &str) -> Result<()> {
         let mut watchers = self.watchers.write().await;
         if let Some(handle) = watchers.remove(watch_id) {
@@ -440,3 +624,71 @@ impl WatchManager {
         }
     }
 }
+
+struct RolloutFollow {
+    app: AppHandle,
+    event_name: String,
+    name: String,
+    timeout: std::time::Duration,
+}
+
+impl RolloutFollow {
+    /// Emit status updates until the rollout finishes or times out. `last` is the
+    /// status before the watch started, so a timeout is reported even if no
+    /// watch event ever arrives.
+    async fn run<K>(
+        &self,
+        api: Api<K>,
+        status_of: fn(&K) -> RolloutStatus,
+        mut last: RolloutStatus,
+        mut shutdown_rx: tokio::sync::oneshot::Receiver<()>,
+    ) where
+        K: kube::Resource + Clone + serde::de::DeserializeOwned + std::fmt::Debug + Send + 'static,
+    {
+        let config = watcher::Config::default().fields(&format!("metadata.name={}", self.name));
+        let watcher_stream = watcher::watcher(api, config);
+        futures::pin_mut!(watcher_stream);
+        let deadline = tokio::time::sleep(self.timeout);
+        futures::pin_mut!(deadline);
+
+        loop {
+            tokio::select! {
+                _ = &mut shutdown_rx => break,
+                _ = &mut deadline => {
+                    let message = format!("Timed out after {}s: {}", self.timeout.as_secs(), last.message);
+                    self.emit(&last.finish(RolloutPhase::TimedOut, message));
+                    break;
+                }
+                event = watcher_stream.try_next() => {
+                    match event {
+                        Ok(Some(Event::Apply(obj))) | Ok(Some(Event::InitApply(obj))) => {
+                            let status = status_of(&obj);
+                            self.emit(&status);
+                            if status.phase.is_finished() {
+                                break;
+                            }
+                            last = status;
+                        }
+                        Ok(Some(Event::Delete(obj))) => {
+                            let status = status_of(&obj);
+                            let message = format!("{} {} was deleted", status.kind.as_str(), status.name);
+                            self.emit(&status.finish(RolloutPhase::Failed, message));
+                            break;
+                        }
+                        Ok(Some(Event::Init)) | Ok(Some(Event::InitDone)) => {}
+                        Ok(None) => break,
+                        Err(e) => {
+                            tracing::warn!("Rollout watch {} error (will retry): {}", self.event_name, e);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn emit(&self, status: &RolloutStatus) {
+        if let Err(e) = self.app.emit(&self.event_name, status) {
+            tracing::error!("Failed to emit rollout status: {}", e);
+        }
+    }
+}
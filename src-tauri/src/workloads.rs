@@ -0,0 +1,774 @@
+use k8s_openapi::api::apps::v1::{DaemonSet, Deployment, StatefulSet};
+use k8s_openapi::api::batch::v1::{CronJob, Job};
+use k8s_openapi::api::core::v1::{LimitRange, ResourceQuota};
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use kube::api::{Api, DynamicObject, ListParams, Patch, PatchParams, PostParams};
+use kube::discovery::ApiResource;
+use kube::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{AppError, Result};
+use crate::kubernetes;
+use crate::rollout::{self, RolloutKind, RolloutStatus};
+
+const CHANGE_CAUSE_ANNOTATION: &str = "kubernetes.io/change-cause";
+/// Labels the Job controller adds to a Job and its pod template; a clone gets new ones
+const JOB_CONTROLLER_LABELS: [&str; 4] = [
+    "controller-uid",
+    "batch.kubernetes.io/controller-uid",
+    "job-name",
+    "batch.kubernetes.io/job-name",
+];
+
+/// Kinds that own a pod template
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum WorkloadKind {
+    Deployment,
+    StatefulSet,
+    DaemonSet,
+    CronJob,
+    Job,
+}
+
+impl WorkloadKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkloadKind::Deployment => "Deployment",
+            WorkloadKind::StatefulSet => "StatefulSet",
+            WorkloadKind::DaemonSet => "DaemonSet",
+            WorkloadKind::CronJob => "CronJob",
+            WorkloadKind::Job => "Job",
+        }
+    }
+
+    fn api_resource(&self) -> ApiResource {
+        match self {
+            WorkloadKind::Deployment => ApiResource::erase::<Deployment>(&()),
+            WorkloadKind::StatefulSet => ApiResource::erase::<StatefulSet>(&()),
+            WorkloadKind::DaemonSet => ApiResource::erase::<DaemonSet>(&()),
+            WorkloadKind::CronJob => ApiResource::erase::<CronJob>(&()),
+            WorkloadKind::Job => ApiResource::erase::<Job>(&()),
+        }
+    }
+
+    /// Path of the pod template within the object
+    fn template_path(&self) -> &'static [&'static str] {
+        match self {
+            WorkloadKind::CronJob => &["spec", "jobTemplate", "spec", "template"],
+            _ => &["spec", "template"],
+        }
+    }
+
+    /// Kinds with a rollout that can be waited on
+    pub fn rollout_kind(&self) -> Option<RolloutKind> {
+        match self {
+            WorkloadKind::Deployment => Some(RolloutKind::Deployment),
+            WorkloadKind::StatefulSet => Some(RolloutKind::StatefulSet),
+            WorkloadKind::DaemonSet => Some(RolloutKind::DaemonSet),
+            WorkloadKind::CronJob | WorkloadKind::Job => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetImageResult {
+    pub container: String,
+    pub previous_image: Option<String>,
+    pub image: String,
+    /// Final rollout status, when asked to wait
+    pub rollout: Option<RolloutStatus>,
+}
+
+/// CPU and memory requests/limits of one container, as Quantity strings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContainerResources {
+    pub cpu_request: Option<String>,
+    pub cpu_limit: Option<String>,
+    pub memory_request: Option<String>,
+    pub memory_limit: Option<String>,
+}
+
+impl ContainerResources {
+    fn from_container(container: &Value) -> Self {
+        let get = |section: &str, resource: &str| {
+            container
+                .pointer(&format!("/resources/{}/{}", section, resource))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        };
+        Self {
+            cpu_request: get("requests", "cpu"),
+            cpu_limit: get("limits", "cpu"),
+            memory_request: get("requests", "memory"),
+            memory_limit: get("limits", "memory"),
+        }
+    }
+
+    /// `(request, limit)` for "cpu" or "memory"
+    fn pair(&self, resource: &str) -> (Option<&str>, Option<&str>) {
+        match resource {
+            "cpu" => (self.cpu_request.as_deref(), self.cpu_limit.as_deref()),
+            _ => (self.memory_request.as_deref(), self.memory_limit.as_deref()),
+        }
+    }
+
+    /// Apply an update: `None` keeps a value, an empty string removes it
+    fn updated(&self, update: &ContainerResources) -> Self {
+        let merge = |current: &Option<String>, new: &Option<String>| match new.as_deref().map(str::trim) {
+            None => current.clone(),
+            Some("") => None,
+            Some(value) => Some(value.to_string()),
+        };
+        Self {
+            cpu_request: merge(&self.cpu_request, &update.cpu_request),
+            cpu_limit: merge(&self.cpu_limit, &update.cpu_limit),
+            memory_request: merge(&self.memory_request, &update.memory_request),
+            memory_limit: merge(&self.memory_limit, &update.memory_limit),
+        }
+    }
+}
+
+/// A requests/limits change, checked but not yet applied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourcesPlan {
+    pub container: String,
+    pub current: ContainerResources,
+    pub proposed: ContainerResources,
+    /// Strategic merge patch that will be sent
+    pub patch: Value,
+    /// Problems that would get the pods rejected; the update is refused while any remain
+    pub violations: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Changes to apply to a container when re-running a Job
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JobOverrides {
+    /// Container to change; may be omitted when the Job has only one
+    pub container: Option<String>,
+    pub image: Option<String>,
+    /// Replaces the container's command
+    pub command: Option<Vec<String>>,
+    pub args: Option<Vec<String>>,
+    /// Set env vars by name; a `None` value removes the variable
+    pub env: Vec<EnvOverride>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvOverride {
+    pub name: String,
+    pub value: Option<String>,
+}
+
+impl JobOverrides {
+    fn is_empty(&self) -> bool {
+        self.image.is_none() && self.command.is_none() && self.args.is_none() && self.env.is_empty()
+    }
+
+    /// Short description for the audit log
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(image) = &self.image {
+            parts.push(format!("image={}", image));
+        }
+        if let Some(command) = &self.command {
+            parts.push(format!("command={:?}", command));
+        }
+        if let Some(args) = &self.args {
+            parts.push(format!("args={:?}", args));
+        }
+        for env in &self.env {
+            match &env.value {
+                Some(_) => parts.push(format!("env {}=<set>", env.name)),
+                None => parts.push(format!("env {}-", env.name)),
+            }
+        }
+        parts.join(" ")
+    }
+}
+
+/// Check an image reference against the distribution reference grammar:
+/// `[registry[:port]/]path[:tag][@digest]`
+pub fn validate_image_reference(image: &str) -> Result<()> {
+    if image.is_empty() {
+        return Err(AppError::Invalid("Image is required".to_string()));
+    }
+    if image.len() > 4096 {
+        return Err(AppError::Invalid("Image reference is too long".to_string()));
+    }
+
+    let reference_regex = regex::Regex::new(concat!(
+        // Optional registry host with port
+        r"^(?:[a-zA-Z0-9](?:[a-zA-Z0-9-]*[a-zA-Z0-9])?(?:\.[a-zA-Z0-9](?:[a-zA-Z0-9-]*[a-zA-Z0-9])?)*(?::[0-9]+)?/)?",
+        // Repository path components
+        r"[a-z0-9]+(?:(?:[._]|__|-+)[a-z0-9]+)*(?:/[a-z0-9]+(?:(?:[._]|__|-+)[a-z0-9]+)*)*",
+        // Optional tag
+        r"(?::[a-zA-Z0-9_][a-zA-Z0-9_.-]{0,127})?",
+        // Optional digest
+        r"(?:@[A-Za-z][A-Za-z0-9]*(?:[-_+.][A-Za-z][A-Za-z0-9]*)*:[0-9a-fA-F]{32,})?$"
+    ))
+    .unwrap();
+    if !reference_regex.is_match(image) {
+        return Err(AppError::Invalid(format!(
+            "'{}' is not a valid image reference (expected [registry/]repository[:tag][@digest], lowercase repository)",
+            image
+        )));
+    }
+    Ok(())
+}
+
+/// A Job's pod template is immutable once the Job exists, so template patches
+/// would only fail with a 422. `instead` says what to do about it.
+fn ensure_template_mutable(kind: WorkloadKind, instead: &str) -> Result<()> {
+    if matches!(kind, WorkloadKind::Job) {
+        return Err(AppError::Invalid(format!(
+            "A Job's pod template can't be changed after it is created: {}",
+            instead
+        )));
+    }
+    Ok(())
+}
+
+/// Set one container's image with a strategic merge patch, recording the change
+/// cause so it shows up in rollout history
+pub async fn set_image(
+    client: &Client,
+    kind: WorkloadKind,
+    namespace: &str,
+    name: &str,
+    container: &str,
+    image: &str,
+    change_cause: Option<&str>,
+) -> Result<SetImageResult> {
+    ensure_template_mutable(kind, &format!("re-run Job {} with an image override instead", name))?;
+    validate_image_reference(image)?;
+
+    let api = workload_api(client, kind, namespace);
+    let obj = api.get(name).await?;
+    let template = pod_template(&obj, kind);
+    let (list_key, current) = find_container(template, container).ok_or_else(|| {
+        AppError::NotFound(format!(
+            "{} {} has no container '{}' (containers: {})",
+            kind.as_str(),
+            name,
+            container,
+            container_names(template).join(", ")
+        ))
+    })?;
+    let previous_image = current.get("image").and_then(Value::as_str).map(str::to_string);
+
+    let change_cause = change_cause
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("set image {}={}", container, image));
+    let template_patch = serde_json::json!({
+        "spec": {
+            list_key: [{ "name": container, "image": image }]
+        }
+    });
+    let mut patch = nest(kind.template_path(), template_patch);
+    patch["metadata"] = serde_json::json!({
+        "annotations": { CHANGE_CAUSE_ANNOTATION: change_cause }
+    });
+
+    api.patch(name, &PatchParams::default(), &Patch::Strategic(&patch)).await?;
+
+    Ok(SetImageResult {
+        container: container.to_string(),
+        previous_image,
+        image: image.to_string(),
+        rollout: None,
+    })
+}
+
+/// Create a new Job from a finished one, dropping everything the Job controller
+/// generated (selector, controller-uid/job-name labels, status) so it gets fresh
+/// ones. Returns the new Job's name.
+pub async fn rerun_job(
+    client: &Client,
+    namespace: &str,
+    name: &str,
+    new_name: Option<&str>,
+    overrides: &JobOverrides,
+) -> Result<String> {
+    let jobs: Api<Job> = Api::namespaced(client.clone(), namespace);
+    let job = jobs.get(name).await?;
+
+    let finished = job
+        .status
+        .as_ref()
+        .and_then(|s| s.conditions.as_ref())
+        .is_some_and(|c| {
+            c.iter()
+                .any(|c| c.status == "True" && (c.type_ == "Complete" || c.type_ == "Failed"))
+        });
+    if !finished {
+        return Err(AppError::Conflict(format!(
+            "Job {} has not finished yet; wait for it to complete or fail before re-running it",
+            name
+        )));
+    }
+
+    let new_name = match new_name.map(str::trim).filter(|n| !n.is_empty()) {
+        Some(n) => n.to_string(),
+        // Re-running a re-run doesn't stack suffixes
+        None => kubernetes::generated_job_name(name.split("-rerun-").next().unwrap_or(name), "rerun"),
+    };
+
+    let strip_labels = |labels: Option<std::collections::BTreeMap<String, String>>| {
+        labels
+            .map(|mut l| {
+                l.retain(|k, _| !JOB_CONTROLLER_LABELS.contains(&k.as_str()));
+                l
+            })
+            .filter(|l| !l.is_empty())
+    };
+
+    let mut spec = job.spec.unwrap_or_default();
+    spec.selector = None;
+    spec.manual_selector = None;
+    if let Some(meta) = spec.template.metadata.as_mut() {
+        meta.labels = strip_labels(meta.labels.take());
+    }
+    if !overrides.is_empty() {
+        let pod_spec = spec
+            .template
+            .spec
+            .as_mut()
+            .ok_or_else(|| AppError::Invalid(format!("Job {} has no pod spec", name)))?;
+        apply_job_overrides(pod_spec, overrides)?;
+    }
+
+    let mut annotations = job.metadata.annotations.unwrap_or_default();
+    annotations.remove("kubectl.kubernetes.io/last-applied-configuration");
+
+    let clone = Job {
+        metadata: k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
+            name: Some(new_name.clone()),
+            namespace: Some(namespace.to_string()),
+            labels: strip_labels(job.metadata.labels),
+            annotations: Some(annotations).filter(|a| !a.is_empty()),
+            ..Default::default()
+        },
+        spec: Some(spec),
+        status: None,
+    };
+    jobs.create(&PostParams::default(), &clone).await?;
+    Ok(new_name)
+}
+
+fn apply_job_overrides(pod_spec: &mut k8s_openapi::api::core::v1::PodSpec, overrides: &JobOverrides) -> Result<()> {
+    use k8s_openapi::api::core::v1::EnvVar;
+
+    let names: Vec<String> = pod_spec.containers.iter().map(|c| c.name.clone()).collect();
+    let container = match overrides.container.as_deref() {
+        Some(target) => pod_spec.containers.iter_mut().find(|c| c.name == target).ok_or_else(|| {
+            AppError::NotFound(format!("No container '{}' (containers: {})", target, names.join(", ")))
+        })?,
+        None if pod_spec.containers.len() == 1 => &mut pod_spec.containers[0],
+        None => {
+            return Err(AppError::Invalid(format!(
+                "The Job has several containers, choose one of: {}",
+                names.join(", ")
+            )))
+        }
+    };
+
+    if let Some(image) = &overrides.image {
+        validate_image_reference(image)?;
+        container.image = Some(image.clone());
+    }
+    if let Some(command) = &overrides.command {
+        container.command = Some(command.clone()).filter(|c| !c.is_empty());
+    }
+    if let Some(args) = &overrides.args {
+        container.args = Some(args.clone()).filter(|a| !a.is_empty());
+    }
+    if !overrides.env.is_empty() {
+        let env = container.env.get_or_insert_with(Vec::new);
+        for change in &overrides.env {
+            if change.name.trim().is_empty() {
+                return Err(AppError::Invalid("Env var name must not be empty".to_string()));
+            }
+            let existing = env.iter().position(|e| e.name == change.name);
+            match (&change.value, existing) {
+                // Keep the variable's position, later entries may refer to it
+                (Some(value), Some(index)) => {
+                    env[index].value = Some(value.clone());
+                    env[index].value_from = None;
+                }
+                (Some(value), None) => env.push(EnvVar {
+                    name: change.name.clone(),
+                    value: Some(value.clone()),
+                    value_from: None,
+                }),
+                (None, Some(index)) => {
+                    env.remove(index);
+                }
+                (None, None) => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Work out and check a requests/limits change for one container against the
+/// namespace's LimitRanges and ResourceQuotas, without applying it
+pub async fn plan_resources(
+    client: &Client,
+    kind: WorkloadKind,
+    namespace: &str,
+    name: &str,
+    container: &str,
+    update: &ContainerResources,
+) -> Result<ResourcesPlan> {
+    ensure_template_mutable(kind, "change the resources in its CronJob, or delete and recreate the Job")?;
+    let obj = workload_api(client, kind, namespace).get(name).await?;
+    let template = pod_template(&obj, kind);
+    let (list_key, current_container) = find_container(template, container).ok_or_else(|| {
+        AppError::NotFound(format!("{} {} has no container '{}'", kind.as_str(), name, container))
+    })?;
+
+    let current = ContainerResources::from_container(current_container);
+    let proposed = current.updated(update);
+
+    let mut violations = Vec::new();
+    let mut warnings = Vec::new();
+    for resource in ["cpu", "memory"] {
+        let (request, limit) = proposed.pair(resource);
+        let request = request.map(parse_quantity).transpose()?;
+        let limit = limit.map(parse_quantity).transpose()?;
+        if let (Some(request), Some(limit)) = (request, limit) {
+            if request > limit {
+                violations.push(format!("{} request must not exceed its limit", resource));
+            }
+        }
+    }
+
+    let limit_ranges = Api::<LimitRange>::namespaced(client.clone(), namespace)
+        .list(&ListParams::default())
+        .await?;
+    for limit_range in &limit_ranges.items {
+        check_limit_range(limit_range, &proposed, &mut violations)?;
+    }
+
+    let quotas = Api::<ResourceQuota>::namespaced(client.clone(), namespace)
+        .list(&ListParams::default())
+        .await?;
+    let replicas = replica_count(&obj, kind);
+    for quota in &quotas.items {
+        check_quota(quota, &current, &proposed, replicas, &mut violations, &mut warnings)?;
+    }
+
+    let patch = resources_patch(kind, list_key, container, &current, &proposed);
+    Ok(ResourcesPlan {
+        container: container.to_string(),
+        current,
+        proposed,
+        patch,
+        violations,
+        warnings,
+    })
+}
+
+/// Apply a requests/limits change after checking it. Refused if the plan has violations.
+pub async fn set_resources(
+    client: &Client,
+    kind: WorkloadKind,
+    namespace: &str,
+    name: &str,
+    container: &str,
+    update: &ContainerResources,
+) -> Result<ResourcesPlan> {
+    let plan = plan_resources(client, kind, namespace, name, container, update).await?;
+    if !plan.violations.is_empty() {
+        return Err(AppError::Invalid(plan.violations.join("; ")));
+    }
+    workload_api(client, kind, namespace)
+        .patch(name, &PatchParams::default(), &Patch::Strategic(&plan.patch))
+        .await?;
+    Ok(plan)
+}
+
+/// Parse a Kubernetes Quantity (e.g. `500m`, `1.5`, `256Mi`, `1e3`) to base units
+pub fn parse_quantity(quantity: &str) -> Result<f64> {
+    let quantity_regex = regex::Regex::new(
+        r"^([0-9]+(?:\.[0-9]*)?|\.[0-9]+)(Ki|Mi|Gi|Ti|Pi|Ei|n|u|m|k|M|G|T|P|E|[eE][+-]?[0-9]+)?$",
+    )
+    .unwrap();
+    let invalid = || {
+        AppError::Invalid(format!(
+            "'{}' is not a valid quantity (e.g. 250m, 0.5, 512Mi, 2Gi)",
+            quantity
+        ))
+    };
+    let captures = quantity_regex.captures(quantity).ok_or_else(invalid)?;
+    let number: f64 = captures[1].parse().map_err(|_| invalid())?;
+    let multiplier = match captures.get(2).map(|m| m.as_str()) {
+        None => 1.0,
+        Some("n") => 1e-9,
+        Some("u") => 1e-6,
+        Some("m") => 1e-3,
+        Some("k") => 1e3,
+        Some("M") => 1e6,
+        Some("G") => 1e9,
+        Some("T") => 1e12,
+        Some("P") => 1e15,
+        Some("E") => 1e18,
+        Some("Ki") => 1024f64,
+        Some("Mi") => 1024f64.powi(2),
+        Some("Gi") => 1024f64.powi(3),
+        Some("Ti") => 1024f64.powi(4),
+        Some("Pi") => 1024f64.powi(5),
+        Some("Ei") => 1024f64.powi(6),
+        Some(exponent) => 10f64.powi(exponent[1..].parse().map_err(|_| invalid())?),
+    };
+    Ok(number * multiplier)
+}
+
+/// Check the container-level constraints of a LimitRange, taking its defaults
+/// into account for values left unset
+fn check_limit_range(
+    limit_range: &LimitRange,
+    proposed: &ContainerResources,
+    violations: &mut Vec<String>,
+) -> Result<()> {
+    let range_name = limit_range.metadata.name.as_deref().unwrap_or_default();
+    let items = limit_range.spec.iter().flat_map(|s| &s.limits).filter(|l| l.type_ == "Container");
+    for item in items {
+        for resource in ["cpu", "memory"] {
+            let value = |map: &Option<std::collections::BTreeMap<String, Quantity>>| {
+                map.as_ref()
+                    .and_then(|m| m.get(resource))
+                    .map(|q| parse_quantity(&q.0))
+                    .transpose()
+            };
+            let (request, limit) = proposed.pair(resource);
+            let limit = match limit {
+                Some(limit) => Some(parse_quantity(limit)?),
+                None => value(&item.default)?,
+            };
+            let request = match request {
+                Some(request) => Some(parse_quantity(request)?),
+                None => value(&item.default_request)?.or(limit),
+            };
+
+            if let Some(max) = value(&item.max)? {
+                match limit {
+                    Some(limit) if limit > max => violations.push(format!(
+                        "LimitRange {}: {} limit {} exceeds the maximum {}",
+                        range_name,
+                        resource,
+                        format_quantity(resource, limit),
+                        format_quantity(resource, max)
+                    )),
+                    None => violations.push(format!(
+                        "LimitRange {}: a {} limit is required (maximum {})",
+                        range_name,
+                        resource,
+                        format_quantity(resource, max)
+                    )),
+                    _ => {}
+                }
+            }
+            if let (Some(min), Some(request)) = (value(&item.min)?, request) {
+                if request < min {
+                    violations.push(format!(
+                        "LimitRange {}: {} request {} is below the minimum {}",
+                        range_name,
+                        resource,
+                        format_quantity(resource, request),
+                        format_quantity(resource, min)
+                    ));
+                }
+            }
+            if let (Some(ratio), Some(request), Some(limit)) = (value(&item.max_limit_request_ratio)?, request, limit) {
+                if request > 0.0 && limit / request > ratio {
+                    violations.push(format!(
+                        "LimitRange {}: {} limit/request ratio {:.2} exceeds {}",
+                        range_name,
+                        resource,
+                        limit / request,
+                        ratio
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check the change in total requests/limits (per pod change times replicas)
+/// against each quota's remaining headroom
+fn check_quota(
+    quota: &ResourceQuota,
+    current: &ContainerResources,
+    proposed: &ContainerResources,
+    replicas: f64,
+    violations: &mut Vec<String>,
+    warnings: &mut Vec<String>,
+) -> Result<()> {
+    let quota_name = quota.metadata.name.as_deref().unwrap_or_default();
+    let hard = quota.spec.as_ref().and_then(|s| s.hard.as_ref());
+    let used = quota.status.as_ref().and_then(|s| s.used.as_ref());
+
+    for resource in ["cpu", "memory"] {
+        let (current_request, current_limit) = current.pair(resource);
+        let (proposed_request, proposed_limit) = proposed.pair(resource);
+        let tracked = [
+            (format!("requests.{}", resource), current_request, proposed_request),
+            (resource.to_string(), current_request, proposed_request),
+            (format!("limits.{}", resource), current_limit, proposed_limit),
+        ];
+        for (key, old, new) in tracked {
+            let Some(hard_value) = hard.and_then(|h| h.get(&key)) else {
+                continue;
+            };
+            if new.is_none() {
+                warnings.push(format!(
+                    "ResourceQuota {} tracks {}: pods without it are rejected unless a LimitRange sets a default",
+                    quota_name, key
+                ));
+                continue;
+            }
+            let hard_value = parse_quantity(&hard_value.0)?;
+            let used_value = used
+                .and_then(|u| u.get(&key))
+                .map(|q| parse_quantity(&q.0))
+                .transpose()?
+                .unwrap_or(0.0);
+            let old = old.map(parse_quantity).transpose()?.unwrap_or(0.0);
+            let new = new.map(parse_quantity).transpose()?.unwrap_or(0.0);
+            let delta = (new - old) * replicas;
+            if delta > 0.0 && used_value + delta > hard_value {
+                violations.push(format!(
+                    "ResourceQuota {}: {} would reach {} of {} ({} in use)",
+                    quota_name,
+                    key,
+                    format_quantity(resource, used_value + delta),
+                    format_quantity(resource, hard_value),
+                    format_quantity(resource, used_value)
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Pods the workload runs at once, for quota arithmetic
+fn replica_count(obj: &DynamicObject, kind: WorkloadKind) -> f64 {
+    let pointer = match kind {
+        WorkloadKind::Deployment | WorkloadKind::StatefulSet => "/spec/replicas",
+        WorkloadKind::DaemonSet => "/status/desiredNumberScheduled",
+        WorkloadKind::Job => "/spec/parallelism",
+        WorkloadKind::CronJob => "/spec/jobTemplate/spec/parallelism",
+    };
+    obj.data.pointer(pointer).and_then(Value::as_f64).unwrap_or(1.0)
+}
+
+/// Strategic merge patch setting only the changed values; removed values are nulled
+fn resources_patch(
+    kind: WorkloadKind,
+    list_key: &str,
+    container: &str,
+    current: &ContainerResources,
+    proposed: &ContainerResources,
+) -> Value {
+    let mut requests = serde_json::Map::new();
+    let mut limits = serde_json::Map::new();
+    for resource in ["cpu", "memory"] {
+        let (old_request, old_limit) = current.pair(resource);
+        let (new_request, new_limit) = proposed.pair(resource);
+        if old_request != new_request {
+            requests.insert(resource.to_string(), serde_json::json!(new_request));
+        }
+        if old_limit != new_limit {
+            limits.insert(resource.to_string(), serde_json::json!(new_limit));
+        }
+    }
+
+    let mut resources = serde_json::Map::new();
+    if !requests.is_empty() {
+        resources.insert("requests".to_string(), Value::Object(requests));
+    }
+    if !limits.is_empty() {
+        resources.insert("limits".to_string(), Value::Object(limits));
+    }
+    nest(
+        kind.template_path(),
+        serde_json::json!({
+            "spec": {
+                list_key: [{ "name": container, "resources": resources }]
+            }
+        }),
+    )
+}
+
+fn format_quantity(resource: &str, value: f64) -> String {
+    if resource == "cpu" {
+        if value < 1.0 {
+            format!("{}m", (value * 1000.0).round())
+        } else {
+            format!("{}", (value * 1000.0).round() / 1000.0)
+        }
+    } else if value >= 1024f64.powi(3) {
+        format!("{:.2}Gi", value / 1024f64.powi(3))
+    } else {
+        format!("{:.0}Mi", value / 1024f64.powi(2))
+    }
+}
+
+/// Wait for the rollout started by a change, for kinds that have one
+pub async fn wait_for_rollout(
+    client: &Client,
+    kind: WorkloadKind,
+    namespace: &str,
+    name: &str,
+    timeout: std::time::Duration,
+) -> Result<Option<RolloutStatus>> {
+    match kind.rollout_kind() {
+        Some(rollout_kind) => Ok(Some(
+            rollout::wait_for_rollout(client, rollout_kind, namespace, name, timeout).await?,
+        )),
+        None => Ok(None),
+    }
+}
+
+fn workload_api(client: &Client, kind: WorkloadKind, namespace: &str) -> Api<DynamicObject> {
+    Api::namespaced_with(client.clone(), namespace, &kind.api_resource())
+}
+
+fn pod_template(obj: &DynamicObject, kind: WorkloadKind) -> &Value {
+    kind.template_path()
+        .iter()
+        .try_fold(&obj.data, |value, key| value.get(key))
+        .unwrap_or(&Value::Null)
+}
+
+/// A container by name, with the list it was found in
+fn find_container<'a>(template: &'a Value, name: &str) -> Option<(&'static str, &'a Value)> {
+    ["containers", "initContainers"].into_iter().find_map(|list_key| {
+        template
+            .pointer(&format!("/spec/{}", list_key))?
+            .as_array()?
+            .iter()
+            .find(|c| c.get("name").and_then(Value::as_str) == Some(name))
+            .map(|c| (list_key, c))
+    })
+}
+
+fn container_names(template: &Value) -> Vec<&str> {
+    ["containers", "initContainers"]
+        .into_iter()
+        .filter_map(|list_key| template.pointer(&format!("/spec/{}", list_key))?.as_array())
+        .flatten()
+        .filter_map(|c| c.get("name").and_then(Value::as_str))
+        .collect()
+}
+
+/// Wrap `value` in objects along `path`
+fn nest(path: &[&str], value: Value) -> Value {
+    path.iter().rev().fold(value, |inner, key| serde_json::json!({ *key: inner }))
+}
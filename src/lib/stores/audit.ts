@@ -0,0 +1,33 @@
+import { invoke } from '@tauri-apps/api/core';
+
+export interface AuditEntry {
+  timestamp: string;
+  context: string;
+  user: string | null;
+  verb: string;
+  kind: string;
+  namespace: string | null;
+  name: string | null;
+  payload: string | null;
+  success: boolean;
+  error: string | null;
+  // Objects changed by an action on several objects; omitted when empty
+  objects?: string[];
+}
+
+export interface AuditQuery {
+  context?: string;
+  verb?: string;
+  kind?: string;
+  namespace?: string;
+  name?: string;
+  success?: boolean;
+  since?: string;
+  until?: string;
+  limit?: number;
+}
+
+// Newest entries first
+export async function queryAuditLog(query: AuditQuery = {}): Promise<AuditEntry[]> {
+  return invoke<AuditEntry[]>('get_audit_log', { query });
+}
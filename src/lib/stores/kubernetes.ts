@@ -1,4 +1,6 @@
-import { writable, derived } from 'svelte/store';
+import { writable, derived, get } from 'svelte/store';
+import { confirmMutation, MutationCancelled } from './settings';
+import { errorMessage, type AppErrorPayload } from '../utils/errors';
 
 // Lazy invoke helper to ensure Tauri is ready
 async function tauriInvoke<T>(cmd: string, args?: Record<string, unknown>): Promise<T> {
@@ -262,6 +264,34 @@ export interface ResourceCount {
   fail: number;
 }
 
+export interface ApiResourceInfo {
+  group: string;
+  version: string;
+  api_version: string;
+  kind: string;
+  plural: string;
+  singular: string;
+  short_names: string[];
+  namespaced: boolean;
+  verbs: string[];
+  subresources: string[];
+  custom: boolean;
+}
+
+export interface ClusterApiInfo {
+  server_version: {
+    major: string;
+    minor: string;
+    git_version: string;
+    platform: string;
+    build_date: string;
+  };
+  groups: { name: string; versions: string[]; preferred_version: string | null }[];
+  resources: ApiResourceInfo[];
+  failed_group_versions: string[];
+  discovered_at: string;
+}
+
 export interface PulseMetrics {
   context: string;
   cluster: string;
@@ -329,6 +359,7 @@ export const clusterEvents = writable<ClusterEventInfo[]>([]);
 
 export const clusterMetrics = writable<ClusterMetrics | null>(null);
 export const pulseMetrics = writable<PulseMetrics | null>(null);
+export const apiInfo = writable<ClusterApiInfo | null>(null);
 export const isLoading = writable<boolean>(false);
 export const error = writable<string | null>(null);
 
@@ -402,12 +433,13 @@ export async function loadContexts() {
     currentContext.set(current);
     connectionStatus.set('connected');
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
     connectionStatus.set('disconnected');
   }
 }
 
-export async function switchContext(contextName: string) {
+// Switches the app's context; `persist` also writes current-context to kubeconfig
+export async function switchContext(contextName: string, persist = false) {
   try {
     isLoading.set(true);
     // Stop any active watch streams before switching
@@ -416,7 +448,7 @@ export async function switchContext(contextName: string) {
     await stopJobWatch();
     await stopNodeWatch();
     await stopEventWatch();
-    await tauriInvoke('switch_context', { contextName });
+    await tauriInvoke('switch_context', { contextName, persist });
     currentContext.set(contextName);
     // Reset namespace selection when switching clusters
     selectedNamespace.set(null);
@@ -425,8 +457,9 @@ export async function switchContext(contextName: string) {
     // Reload namespaces for the new cluster
     await loadNamespaces();
     await loadClusterMetrics();
+    await loadApiResources();
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   } finally {
     isLoading.set(false);
   }
@@ -438,7 +471,7 @@ export async function loadNamespaces() {
     const ns = await tauriInvoke<string[]>('get_namespaces');
     namespaces.set(ns);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   }
 }
 
@@ -449,7 +482,7 @@ export async function loadPods(namespace?: string | null) {
     const podList = await tauriInvoke<PodInfo[]>('get_pods', { namespace });
     pods.set(podList);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   } finally {
     isLoading.set(false);
   }
@@ -498,7 +531,7 @@ export async function startPodWatch(namespace?: string | null) {
 
     console.log(`Pod watch started: ${watchId}`);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
     console.error('Failed to start pod watch:', e);
   }
 }
@@ -558,7 +591,7 @@ export async function startDeploymentWatch(namespace?: string | null) {
 
     console.log(`Deployment watch started: ${watchId}`);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
     console.error('Failed to start deployment watch:', e);
   }
 }
@@ -618,7 +651,7 @@ export async function startJobWatch(namespace?: string | null) {
 
     console.log(`Job watch started: ${watchId}`);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
     console.error('Failed to start job watch:', e);
   }
 }
@@ -678,7 +711,7 @@ export async function startNodeWatch() {
 
     console.log(`Node watch started: ${watchId}`);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
     console.error('Failed to start node watch:', e);
   }
 }
@@ -739,7 +772,7 @@ export async function startEventWatch(namespace?: string | null) {
 
     console.log(`Event watch started: ${watchId}`);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
     console.error('Failed to start event watch:', e);
   }
 }
@@ -768,7 +801,7 @@ export async function loadEvents(namespace?: string | null) {
     const eventList = await tauriInvoke<ClusterEventInfo[]>('get_events', { namespace });
     clusterEvents.set(eventList);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   } finally {
     isLoading.set(false);
   }
@@ -781,7 +814,7 @@ export async function loadDeployments(namespace?: string | null) {
     const deployList = await tauriInvoke<DeploymentInfo[]>('get_deployments', { namespace });
     deployments.set(deployList);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   } finally {
     isLoading.set(false);
   }
@@ -794,7 +827,7 @@ export async function loadStatefulSets(namespace?: string | null) {
     const stsList = await tauriInvoke<StatefulSetInfo[]>('get_statefulsets', { namespace });
     statefulsets.set(stsList);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   } finally {
     isLoading.set(false);
   }
@@ -802,12 +835,310 @@ export async function loadStatefulSets(namespace?: string | null) {
 
 export async function scaleStatefulSet(namespace: string, name: string, replicas: number) {
   if (!isContextReady()) return;
-  await tauriInvoke('scale_statefulset', { namespace, name, replicas });
+  const contextName = get(currentContext);
+  const confirm = await confirmMutation(contextName, 'Scale');
+  await tauriInvoke('scale_statefulset', { contextName, namespace, name, replicas, confirm });
 }
 
 export async function restartStatefulSet(namespace: string, name: string) {
   if (!isContextReady()) return;
-  await tauriInvoke('restart_statefulset', { namespace, name });
+  const contextName = get(currentContext);
+  const confirm = await confirmMutation(contextName, 'Restart');
+  await tauriInvoke('restart_statefulset', { contextName, namespace, name, confirm });
+}
+
+export async function restartDaemonSet(namespace: string, name: string) {
+  if (!isContextReady()) return;
+  const contextName = get(currentContext);
+  const confirm = await confirmMutation(contextName, 'Restart');
+  await tauriInvoke('restart_daemonset', { contextName, namespace, name, confirm });
+}
+
+export async function scaleReplicaSet(namespace: string, name: string, replicas: number) {
+  if (!isContextReady()) return;
+  const contextName = get(currentContext);
+  const confirm = await confirmMutation(contextName, 'Scale');
+  await tauriInvoke('scale_replicaset', { contextName, namespace, name, replicas, confirm });
+}
+
+export type WorkloadKind = 'Deployment' | 'StatefulSet' | 'DaemonSet' | 'CronJob' | 'Job';
+
+export interface SetImageResult {
+  container: string;
+  previous_image: string | null;
+  image: string;
+  // Set when waiting for the rollout
+  rollout: RolloutStatus | null;
+}
+
+export async function setContainerImage(
+  contextName: string,
+  kind: WorkloadKind,
+  namespace: string,
+  name: string,
+  container: string,
+  image: string,
+  options: { changeCause?: string; wait?: boolean; timeoutSeconds?: number } = {}
+): Promise<SetImageResult> {
+  const confirm = await confirmMutation(contextName, 'Set image');
+  return tauriInvoke<SetImageResult>('set_container_image', {
+    contextName,
+    kind,
+    namespace,
+    name,
+    container,
+    image,
+    changeCause: options.changeCause ?? null,
+    wait: options.wait ?? false,
+    timeoutSeconds: options.timeoutSeconds ?? null,
+    confirm,
+  });
+}
+
+// Quantity strings; undefined keeps a value, '' removes it
+export interface ContainerResources {
+  cpu_request?: string | null;
+  cpu_limit?: string | null;
+  memory_request?: string | null;
+  memory_limit?: string | null;
+}
+
+export interface ResourcesPlan {
+  container: string;
+  current: ContainerResources;
+  proposed: ContainerResources;
+  // Strategic merge patch that will be sent
+  patch: unknown;
+  violations: string[];
+  warnings: string[];
+}
+
+export async function previewContainerResources(
+  contextName: string,
+  kind: WorkloadKind,
+  namespace: string,
+  name: string,
+  container: string,
+  resources: ContainerResources
+): Promise<ResourcesPlan> {
+  return tauriInvoke<ResourcesPlan>('preview_container_resources', {
+    contextName,
+    kind,
+    namespace,
+    name,
+    container,
+    resources,
+  });
+}
+
+export async function setContainerResources(
+  contextName: string,
+  kind: WorkloadKind,
+  namespace: string,
+  name: string,
+  container: string,
+  resources: ContainerResources
+): Promise<ResourcesPlan> {
+  const confirm = await confirmMutation(contextName, 'Set resources');
+  return tauriInvoke<ResourcesPlan>('set_container_resources', {
+    contextName,
+    kind,
+    namespace,
+    name,
+    container,
+    resources,
+    confirm,
+  });
+}
+
+// Rollout history
+export type RolloutKind = 'Deployment' | 'StatefulSet' | 'DaemonSet';
+
+export interface RolloutRevision {
+  revision: number;
+  // ReplicaSet or ControllerRevision holding the revision
+  source: string;
+  change_cause: string | null;
+  images: string[];
+  created: string | null;
+  age: string;
+  current: boolean;
+}
+
+export interface RevisionDiff {
+  from_revision: number;
+  to_revision: number;
+  changed: boolean;
+  lines: { kind: 'unchanged' | 'added' | 'removed'; text: string }[];
+}
+
+export async function getRolloutHistory(contextName: string, kind: RolloutKind, namespace: string, name: string) {
+  return tauriInvoke<RolloutRevision[]>('get_rollout_history', { contextName, kind, namespace, name });
+}
+
+export async function diffRolloutRevisions(
+  contextName: string,
+  kind: RolloutKind,
+  namespace: string,
+  name: string,
+  fromRevision: number,
+  toRevision: number
+) {
+  return tauriInvoke<RevisionDiff>('diff_rollout_revisions', {
+    contextName,
+    kind,
+    namespace,
+    name,
+    fromRevision,
+    toRevision,
+  });
+}
+
+export async function rollbackRollout(
+  contextName: string,
+  kind: RolloutKind,
+  namespace: string,
+  name: string,
+  revision: number
+) {
+  const confirm = await confirmMutation(contextName, `Roll back to revision ${revision}`);
+  await tauriInvoke('rollback_rollout', { contextName, kind, namespace, name, revision, confirm });
+}
+
+export type RolloutPhase = 'progressing' | 'complete' | 'failed' | 'timed_out' | 'unsupported';
+
+export interface RolloutStatus {
+  kind: RolloutKind;
+  namespace: string;
+  name: string;
+  phase: RolloutPhase;
+  message: string;
+  generation: number | null;
+  observed_generation: number | null;
+  desired: number;
+  updated: number;
+  ready: number;
+  available: number;
+  timestamp: string;
+}
+
+// Follow a rollout until it finishes. Returns a function that stops following early.
+export async function followRollout(
+  contextName: string,
+  kind: RolloutKind,
+  namespace: string,
+  name: string,
+  onStatus: (status: RolloutStatus) => void,
+  timeoutSeconds?: number
+): Promise<() => Promise<void>> {
+  const { watch_id: watchId, status } = await tauriInvoke<{ watch_id: string | null; status: RolloutStatus }>(
+    'watch_rollout_status',
+    {
+      contextName,
+      kind,
+      namespace,
+      name,
+      timeoutSeconds: timeoutSeconds ?? null,
+    }
+  );
+  onStatus(status);
+  // Already finished: there is nothing to follow
+  if (!watchId) return async () => {};
+
+  const { listen } = await import('@tauri-apps/api/event');
+  let finished = false;
+  const unlisten = await listen<RolloutStatus>(`rollout-status-${watchId}`, (event) => {
+    onStatus(event.payload);
+    if (event.payload.phase !== 'progressing') {
+      finished = true;
+      unlisten();
+    }
+  });
+  // The watch holds its events until we're listening
+  await tauriInvoke('watch_listener_ready', { watchId });
+
+  return async () => {
+    if (finished) return;
+    finished = true;
+    unlisten();
+    await tauriInvoke('stop_watch', { watchId });
+  };
+}
+
+export interface DrainOptions {
+  delete_emptydir_data?: boolean;
+  force?: boolean;
+  grace_period_seconds?: number | null;
+  timeout_seconds?: number | null;
+}
+
+export type DrainPodPhase = 'pending' | 'skipped' | 'blocked' | 'evicting' | 'evicted' | 'failed';
+
+export interface DrainPod {
+  namespace: string;
+  name: string;
+  phase: DrainPodPhase;
+  message: string | null;
+}
+
+export type DrainStatus = 'running' | 'complete' | 'failed' | 'timed_out' | 'cancelled';
+
+export interface DrainProgress {
+  node: string;
+  status: DrainStatus;
+  // The pod this update is about; null on the final event
+  pod: DrainPod | null;
+  // Every pod's last state, on the final event only
+  pods: DrainPod[];
+  message: string | null;
+  timestamp: string;
+}
+
+export interface DrainPlan {
+  node: string;
+  evict: DrainPod[];
+  skipped: DrainPod[];
+}
+
+// Cordon and drain a node. Resolves once the checks pass and the node is cordoned;
+// progress arrives through `onProgress`. Call `cancel` to stop evicting. `status`
+// is already 'complete' when there was nothing to evict.
+export async function drainNode(
+  contextName: string,
+  name: string,
+  options: DrainOptions,
+  onProgress: (progress: DrainProgress) => void
+): Promise<{ plan: DrainPlan; status: DrainStatus; cancel: () => Promise<void> }> {
+  const confirm = await confirmMutation(contextName, 'Drain node');
+  const { drain_id, status, plan } = await tauriInvoke<{
+    drain_id: string | null;
+    status: DrainStatus;
+    plan: DrainPlan;
+  }>('drain_node', {
+    contextName,
+    name,
+    options,
+    confirm,
+  });
+  if (!drain_id) return { plan, status, cancel: async () => {} };
+
+  const { listen } = await import('@tauri-apps/api/event');
+  let finished = false;
+  const unlisten = await listen<DrainProgress>(`node-drain-${drain_id}`, (event) => {
+    onProgress(event.payload);
+    if (event.payload.status !== 'running') {
+      finished = true;
+      unlisten();
+    }
+  });
+  // The drain holds off evicting until we're listening
+  await tauriInvoke('watch_listener_ready', { watchId: drain_id });
+
+  const cancel = async () => {
+    if (finished) return;
+    await tauriInvoke('stop_watch', { watchId: drain_id });
+  };
+  return { plan, status, cancel };
 }
 
 export async function loadDaemonSets(namespace?: string | null) {
@@ -817,7 +1148,7 @@ export async function loadDaemonSets(namespace?: string | null) {
     const dsList = await tauriInvoke<DaemonSetInfo[]>('get_daemonsets', { namespace });
     daemonsets.set(dsList);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   } finally {
     isLoading.set(false);
   }
@@ -830,7 +1161,7 @@ export async function loadReplicaSets(namespace?: string | null) {
     const rsList = await tauriInvoke<ReplicaSetInfo[]>('get_replicasets', { namespace });
     replicasets.set(rsList);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   } finally {
     isLoading.set(false);
   }
@@ -843,7 +1174,7 @@ export async function loadJobs(namespace?: string | null) {
     const jobList = await tauriInvoke<JobInfo[]>('get_jobs', { namespace });
     jobs.set(jobList);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   } finally {
     isLoading.set(false);
   }
@@ -856,7 +1187,7 @@ export async function loadCronJobs(namespace?: string | null) {
     const cjList = await tauriInvoke<CronJobInfo[]>('get_cronjobs', { namespace });
     cronjobs.set(cjList);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   } finally {
     isLoading.set(false);
   }
@@ -869,7 +1200,7 @@ export async function loadServices(namespace?: string | null) {
     const svcList = await tauriInvoke<ServiceInfo[]>('get_services', { namespace });
     services.set(svcList);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   } finally {
     isLoading.set(false);
   }
@@ -883,7 +1214,7 @@ export async function loadIngresses(namespace?: string | null) {
     const ingList = await tauriInvoke<IngressInfo[]>('get_ingresses', { namespace });
     ingresses.set(ingList);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   } finally {
     isLoading.set(false);
   }
@@ -896,7 +1227,7 @@ export async function loadNetworkPolicies(namespace?: string | null) {
     const npList = await tauriInvoke<NetworkPolicyInfo[]>('get_network_policies', { namespace });
     networkPolicies.set(npList);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   } finally {
     isLoading.set(false);
   }
@@ -910,7 +1241,7 @@ export async function loadConfigMaps(namespace?: string | null) {
     const cmList = await tauriInvoke<ConfigMapInfo[]>('get_configmaps', { namespace });
     configmaps.set(cmList);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   } finally {
     isLoading.set(false);
   }
@@ -923,7 +1254,7 @@ export async function loadSecrets(namespace?: string | null) {
     const secList = await tauriInvoke<SecretInfo[]>('get_secrets', { namespace });
     secrets.set(secList);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   } finally {
     isLoading.set(false);
   }
@@ -936,7 +1267,7 @@ export async function loadHPAs(namespace?: string | null) {
     const hpaList = await tauriInvoke<HPAInfo[]>('get_hpas', { namespace });
     hpas.set(hpaList);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   } finally {
     isLoading.set(false);
   }
@@ -950,7 +1281,7 @@ export async function loadPVs() {
     const pvList = await tauriInvoke<PersistentVolumeInfo[]>('get_pvs');
     pvs.set(pvList);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   } finally {
     isLoading.set(false);
   }
@@ -963,7 +1294,7 @@ export async function loadPVCs(namespace?: string | null) {
     const pvcList = await tauriInvoke<PersistentVolumeClaimInfo[]>('get_pvcs', { namespace });
     pvcs.set(pvcList);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   } finally {
     isLoading.set(false);
   }
@@ -977,7 +1308,7 @@ export async function loadNamespacesInfo() {
     const nsList = await tauriInvoke<NamespaceInfo[]>('get_namespaces_info');
     namespacesInfo.set(nsList);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   } finally {
     isLoading.set(false);
   }
@@ -990,7 +1321,7 @@ export async function loadNodes() {
     const nodeList = await tauriInvoke<NodeInfo[]>('get_nodes');
     nodes.set(nodeList);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   } finally {
     isLoading.set(false);
   }
@@ -1003,7 +1334,7 @@ export async function loadServiceAccounts(namespace?: string | null) {
     const saList = await tauriInvoke<ServiceAccountInfo[]>('get_service_accounts', { namespace });
     serviceAccounts.set(saList);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   } finally {
     isLoading.set(false);
   }
@@ -1015,7 +1346,7 @@ export async function loadClusterMetrics() {
     const metrics = await tauriInvoke<ClusterMetrics>('get_cluster_metrics');
     clusterMetrics.set(metrics);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   }
 }
 
@@ -1025,10 +1356,28 @@ export async function loadPulseMetrics(namespace?: string | null) {
     const metrics = await tauriInvoke<PulseMetrics>('get_pulse_metrics', { namespace });
     pulseMetrics.set(metrics);
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
+  }
+}
+
+export async function loadApiResources(refresh = false) {
+  if (!isContextReady()) return;
+  try {
+    const info = await tauriInvoke<ClusterApiInfo>('get_api_resources', { refresh });
+    apiInfo.set(info);
+  } catch (e) {
+    apiInfo.set(null);
+    error.set(errorMessage(e));
   }
 }
 
+// Whether the cluster serves a resource (group is '' for the core API)
+export function isApiResourceServed(info: ClusterApiInfo | null, group: string, plural: string) {
+  // Assume served until discovery has run
+  if (!info) return true;
+  return info.resources.some((r) => r.group === group && r.plural === plural);
+}
+
 // Load all resources for global search
 export async function loadAllResources(namespace?: string | null) {
   if (!isContextReady()) return;
@@ -1059,13 +1408,222 @@ export async function loadAllResources(namespace?: string | null) {
 export async function deletePod(namespace: string, podName: string) {
   if (!isContextReady()) return;
   try {
-    await tauriInvoke('delete_pod', { namespace, podName });
+    const contextName = get(currentContext);
+    const confirm = await confirmMutation(contextName, 'Delete pod');
+    await tauriInvoke('delete_pod', { contextName, namespace, podName, confirm });
     await loadPods(namespace);
   } catch (e) {
-    error.set(String(e));
+    if (e instanceof MutationCancelled) return;
+    error.set(errorMessage(e));
   }
 }
 
+// Eviction honours PodDisruptionBudgets and fails with a conflict when one blocks it
+export async function evictPod(namespace: string, podName: string, acknowledgeRisk: boolean, gracePeriodSeconds?: number) {
+  if (!isContextReady()) return;
+  try {
+    const contextName = get(currentContext);
+    const confirm = await confirmMutation(contextName, 'Evict pod');
+    await tauriInvoke('evict_pod', {
+      contextName,
+      namespace,
+      podName,
+      gracePeriodSeconds: gracePeriodSeconds ?? null,
+      acknowledgeRisk,
+      confirm,
+    });
+    await loadPods(namespace);
+  } catch (e) {
+    if (e instanceof MutationCancelled) return;
+    error.set(errorMessage(e));
+  }
+}
+
+// Only for pods already terminating; skips waiting for the kubelet
+export async function forceDeletePod(namespace: string, podName: string, acknowledgeRisk: boolean) {
+  if (!isContextReady()) return;
+  try {
+    const contextName = get(currentContext);
+    const confirm = await confirmMutation(contextName, 'Force delete pod');
+    await tauriInvoke('force_delete_pod', { contextName, namespace, podName, acknowledgeRisk, confirm });
+    await loadPods(namespace);
+  } catch (e) {
+    if (e instanceof MutationCancelled) return;
+    error.set(errorMessage(e));
+  }
+}
+
+// Removes the given finalizers, or all of them when none are given. Returns the ones left.
+export async function removeFinalizers(
+  target: { contextName: string; apiVersion?: string; kind: string; namespace?: string | null; name: string },
+  finalizers: string[],
+  acknowledgeRisk: boolean
+): Promise<string[]> {
+  const contextName = target.contextName;
+  const confirm = await confirmMutation(contextName, 'Remove finalizers');
+  return tauriInvoke<string[]>('remove_finalizers', {
+    contextName,
+    apiVersion: target.apiVersion ?? null,
+    kind: target.kind,
+    namespace: target.namespace ?? null,
+    name: target.name,
+    finalizers,
+    acknowledgeRisk,
+    confirm,
+  });
+}
+
+export type PropagationPolicy = 'Foreground' | 'Background' | 'Orphan';
+
+export interface DeleteOptions {
+  grace_period_seconds?: number | null;
+  // Delete immediately, with grace period 0
+  force?: boolean;
+  propagation_policy?: PropagationPolicy | null;
+  dry_run?: boolean;
+}
+
+export interface DeleteResult {
+  namespace: string | null;
+  name: string;
+  success: boolean;
+  // Accepted but still terminating (finalizers, foreground deletion)
+  pending: boolean;
+  error: AppErrorPayload | null;
+}
+
+export interface DeleteTarget {
+  kind: string;
+  apiVersion?: string;
+  namespace?: string | null;
+  // Either names or a label selector
+  names?: string[];
+  labelSelector?: string;
+  // A label selector on a namespaced kind needs a namespace unless this is set
+  allNamespaces?: boolean;
+}
+
+// Delete objects of any kind, including custom resources. Errors for
+// individual objects are returned in the results rather than thrown.
+export async function deleteResources(
+  target: DeleteTarget & { contextName: string },
+  options: DeleteOptions = {}
+): Promise<DeleteResult[]> {
+  const contextName = target.contextName;
+  const confirm = await confirmMutation(contextName, `Delete ${target.kind}`);
+  return tauriInvoke<DeleteResult[]>('delete_resources', {
+    contextName,
+    apiVersion: target.apiVersion ?? null,
+    kind: target.kind,
+    namespace: target.namespace ?? null,
+    names: target.names ?? null,
+    labelSelector: target.labelSelector ?? null,
+    allNamespaces: target.allNamespaces ?? false,
+    options,
+    confirm,
+  });
+}
+
+export type MetadataField = 'Labels' | 'Annotations';
+
+export interface MetadataChanges {
+  // Keys to add or update
+  set?: Record<string, string>;
+  remove?: string[];
+  // Replace keys that already have a different value
+  overwrite?: boolean;
+}
+
+export interface MetadataResult {
+  namespace: string | null;
+  name: string;
+  success: boolean;
+  // Labels or annotations after the update
+  values: Record<string, string>;
+  error: AppErrorPayload | null;
+}
+
+// Change labels or annotations on objects of any kind, by name or label selector.
+// Errors for individual objects are returned in the results rather than thrown.
+export async function updateMetadata(
+  target: DeleteTarget & { contextName: string },
+  field: MetadataField,
+  changes: MetadataChanges
+): Promise<MetadataResult[]> {
+  const contextName = target.contextName;
+  const confirm = await confirmMutation(contextName, `Update ${field.toLowerCase()}`);
+  return tauriInvoke<MetadataResult[]>('update_metadata', {
+    contextName,
+    apiVersion: target.apiVersion ?? null,
+    kind: target.kind,
+    namespace: target.namespace ?? null,
+    names: target.names ?? null,
+    labelSelector: target.labelSelector ?? null,
+    allNamespaces: target.allNamespaces ?? false,
+    field,
+    changes: { set: changes.set ?? {}, remove: changes.remove ?? [], overwrite: changes.overwrite ?? false },
+    confirm,
+  });
+}
+
+export type ApplyOutcome = 'created' | 'configured' | 'unchanged';
+
+export interface ApplyResult {
+  api_version: string;
+  kind: string;
+  namespace: string | null;
+  name: string;
+  // null when this document failed
+  outcome: ApplyOutcome | null;
+  error: AppErrorPayload | null;
+  // Failed on fields owned by another field manager; apply with forceConflicts to take them over
+  field_conflicts: boolean;
+}
+
+// Server-side apply a (multi-document) YAML manifest. Per-document errors are
+// returned in the results; `forceConflicts` takes over fields owned by others.
+export async function applyYaml(
+  contextName: string,
+  yamlContent: string,
+  forceConflicts = false
+): Promise<ApplyResult[]> {
+  const confirm = await confirmMutation(contextName, 'Apply YAML');
+  return tauriInvoke<ApplyResult[]>('apply_yaml', {
+    contextName,
+    yamlContent,
+    forceConflicts,
+    confirm,
+  });
+}
+
+export interface ApplyPreview {
+  api_version: string;
+  kind: string;
+  namespace: string | null;
+  name: string;
+  // null when the dry run failed
+  outcome: ApplyOutcome | null;
+  // e.g. spec.replicas, spec.template.spec.containers[0].image
+  changed_paths: string[];
+  // Live object against the dry-run result, without managedFields, status and resourceVersion
+  lines: { kind: 'unchanged' | 'added' | 'removed'; text: string }[];
+  error: AppErrorPayload | null;
+  field_conflicts: boolean;
+}
+
+// Server-side dry run of applyYaml: shows what would change without persisting anything
+export async function previewApplyYaml(
+  contextName: string,
+  yamlContent: string,
+  forceConflicts = false
+): Promise<ApplyPreview[]> {
+  return tauriInvoke<ApplyPreview[]>('preview_apply_yaml', {
+    contextName,
+    yamlContent,
+    forceConflicts,
+  });
+}
+
 export async function getPodLogs(
   namespace: string,
   podName: string,
@@ -1083,7 +1641,7 @@ export async function getPodLogs(
       previous,
     });
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
     return '';
   }
 }
@@ -1102,7 +1660,7 @@ export async function openTerminalWindow(
       container: container || null,
     });
   } catch (e) {
-    error.set(String(e));
+    error.set(errorMessage(e));
   }
 }
 
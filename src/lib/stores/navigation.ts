@@ -20,7 +20,8 @@ export type View =
   | 'serviceaccounts'
   | 'nodes'
   | 'namespaces'
-  | 'events';
+  | 'events'
+  | 'audit';
 
 export const currentView = writable<View>('dashboard');
 export const sidebarCollapsed = writable<boolean>(false);
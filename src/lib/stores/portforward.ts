@@ -1,4 +1,5 @@
 import { writable, derived } from 'svelte/store';
+import { errorMessage } from '../utils/errors';
 
 // Lazy invoke helper to ensure Tauri is ready
 async function tauriInvoke<T>(cmd: string, args?: Record<string, unknown>): Promise<T> {
@@ -56,7 +57,7 @@ export async function loadPortForwards(): Promise<void> {
     portForwards.set(forwards);
   } catch (e) {
     console.error('Failed to load port forwards:', e);
-    portForwardError.set(String(e));
+    portForwardError.set(errorMessage(e));
   } finally {
     isLoadingPortForwards.set(false);
   }
@@ -86,7 +87,7 @@ export async function startPortForward(
     return info;
   } catch (e) {
     console.error('Failed to start port forward:', e);
-    portForwardError.set(String(e));
+    portForwardError.set(errorMessage(e));
     throw e;
   }
 }
@@ -100,7 +101,7 @@ export async function stopPortForward(id: string): Promise<void> {
     await loadPortForwards();
   } catch (e) {
     console.error('Failed to stop port forward:', e);
-    portForwardError.set(String(e));
+    portForwardError.set(errorMessage(e));
     throw e;
   }
 }
@@ -114,7 +115,7 @@ export async function stopAllPortForwards(): Promise<void> {
     await loadPortForwards();
   } catch (e) {
     console.error('Failed to stop all port forwards:', e);
-    portForwardError.set(String(e));
+    portForwardError.set(errorMessage(e));
     throw e;
   }
 }
@@ -0,0 +1,82 @@
+import { writable } from 'svelte/store';
+import { invoke } from '@tauri-apps/api/core';
+
+export type ProtectionLevel = 'none' | 'protected' | 'read_only';
+
+export interface ContextSettings {
+  color: string | null;
+  label: string | null;
+  protection: ProtectionLevel;
+}
+
+export interface AppSettings {
+  read_only: boolean;
+  contexts: Record<string, ContextSettings>;
+}
+
+export const appSettings = writable<AppSettings>({ read_only: false, contexts: {} });
+
+export async function loadAppSettings(): Promise<AppSettings> {
+  const settings = await invoke<AppSettings>('get_app_settings');
+  appSettings.set(settings);
+  return settings;
+}
+
+// Must match settings::READ_WRITE_CONFIRMATION in the backend
+const READ_WRITE_CONFIRMATION = 'read-write';
+
+const protectionRank: Record<ProtectionLevel, number> = { none: 0, protected: 1, read_only: 2 };
+
+// Turning read-only mode off asks for typed confirmation
+export async function setReadOnlyMode(enabled: boolean) {
+  let confirm: string | null = null;
+  if (!enabled) {
+    confirm = prompt(`Turn off read-only mode?\nType "${READ_WRITE_CONFIRMATION}" to confirm:`);
+    if (confirm === null) throw new MutationCancelled();
+  }
+  await invoke('set_read_only_mode', { enabled, confirm });
+  await loadAppSettings();
+}
+
+// Lowering a context's protection level asks for the context name
+export async function setContextSettings(contextName: string, contextSettings: ContextSettings) {
+  const current = (await loadAppSettings()).contexts[contextName]?.protection ?? 'none';
+  let confirm: string | null = null;
+  if (protectionRank[contextSettings.protection] < protectionRank[current]) {
+    confirm = prompt(`Lower the protection of "${contextName}"?\nType the context name to confirm:`);
+    if (confirm === null) throw new MutationCancelled();
+  }
+  await invoke('set_context_settings', { contextName, contextSettings, confirm });
+  await loadAppSettings();
+}
+
+export class MutationCancelled extends Error {
+  constructor() {
+    super('Cancelled');
+  }
+}
+
+// Ask for typed confirmation before a mutating command on a protected context.
+// Returns the value to pass as the command's `confirm` argument. The backend
+// enforces the same rule, so this only saves a failed round trip.
+export async function confirmMutation(contextName: string, action: string): Promise<string | null> {
+  // Detail windows have their own store instance, so always read fresh settings
+  const settings = await loadAppSettings();
+  if (settings.read_only) {
+    throw new Error('Read-only mode is enabled: changes to the cluster are disabled');
+  }
+  const protection = settings.contexts[contextName]?.protection ?? 'none';
+
+  if (protection === 'read_only') {
+    throw new Error(`Context '${contextName}' is read-only`);
+  }
+  if (protection !== 'protected') {
+    return null;
+  }
+
+  const typed = prompt(`${action} on protected context "${contextName}".\nType the context name to confirm:`);
+  if (typed === null) {
+    throw new MutationCancelled();
+  }
+  return typed;
+}
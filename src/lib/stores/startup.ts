@@ -1,4 +1,5 @@
 import { writable, derived } from 'svelte/store';
+import { errorMessage } from '../utils/errors';
 
 export interface StartupCheck {
   id: string;
@@ -15,6 +16,30 @@ export const startupChecks = writable<StartupCheck[]>([
   { id: 'namespaces', label: 'Fetching namespaces', status: 'pending' },
 ]);
 
+export interface DiagnosticPhase {
+  name: string;
+  status: 'ok' | 'failed' | 'skipped';
+  duration_ms: number | null;
+  message: string | null;
+}
+
+export interface ConnectionDiagnostics {
+  context: string;
+  server: string | null;
+  success: boolean;
+  server_version: string | null;
+  namespace_count: number | null;
+  phases: DiagnosticPhase[];
+  failure: {
+    kind: string;
+    phase: string;
+    message: string;
+    hint: string;
+  } | null;
+  total_ms: number;
+}
+
+export const connectionDiagnostics = writable<ConnectionDiagnostics | null>(null);
 export const isInitialized = writable(false);
 export const initError = writable<string | null>(null);
 
@@ -67,15 +92,16 @@ export async function runStartupChecks(): Promise<boolean> {
 
     // Check 4: Connect to cluster
     updateCheck('connection', { status: 'running' });
-    const [currentContext] = await Promise.all([
-      (async () => {
-        const ctx = await invoke<string>('get_current_context');
-        await invoke('test_cluster_connection');
-        return ctx;
-      })(),
+    const [report] = await Promise.all([
+      invoke<ConnectionDiagnostics>('test_cluster_connection'),
       delay(STEP_DELAY),
     ]);
-    updateCheck('connection', { status: 'success', message: currentContext });
+    connectionDiagnostics.set(report);
+    if (!report.success && report.failure) {
+      throw `${report.failure.message}. ${report.failure.hint}`;
+    }
+    const version = report.server_version ? ` · ${report.server_version}` : '';
+    updateCheck('connection', { status: 'success', message: `${report.context}${version} · ${report.total_ms}ms` });
 
     // Check 5: Fetch namespaces
     updateCheck('namespaces', { status: 'running' });
@@ -90,13 +116,13 @@ export async function runStartupChecks(): Promise<boolean> {
     isInitialized.set(true);
     return true;
   } catch (error) {
-    const errorMessage = String(error);
-    initError.set(errorMessage);
+    const message = errorMessage(error);
+    initError.set(message);
 
     // Mark current running check as error
     startupChecks.update((checks) =>
       checks.map((check) =>
-        check.status === 'running' ? { ...check, status: 'error', message: errorMessage } : check
+        check.status === 'running' ? { ...check, status: 'error', message } : check
       )
     );
 
@@ -0,0 +1,47 @@
+// Helpers for errors returned by Tauri commands
+
+// One field-level problem from a Status, e.g. a failed validation
+export interface StatusCause {
+  field?: string;
+  message?: string;
+  // e.g. FieldValueInvalid, FieldValueRequired
+  reason?: string;
+}
+
+export interface StatusDetails {
+  name?: string;
+  group?: string;
+  kind?: string;
+  causes?: StatusCause[];
+}
+
+// Shape of every error serialized by the backend
+export interface AppErrorPayload {
+  code: string;
+  message: string;
+  // HTTP status and Status reason and details, when the error came from the API server
+  status: number | null;
+  reason: string | null;
+  details: StatusDetails | null;
+}
+
+export function isAppError(e: unknown): e is AppErrorPayload {
+  return typeof e === 'object' && e !== null && 'code' in e && 'message' in e;
+}
+
+export function errorMessage(e: unknown): string {
+  if (isAppError(e)) return e.message;
+  if (e instanceof Error) return e.message;
+  return String(e);
+}
+
+// Field-level errors as "field: message" lines, or the message when there are none
+export function errorLines(e: unknown): string[] {
+  const causes = isAppError(e) ? (e.details?.causes ?? []) : [];
+  if (causes.length === 0) return [errorMessage(e)];
+  return causes.map((c) => (c.field ? `${c.field}: ${c.message ?? ''}` : (c.message ?? '')));
+}
+
+export function errorCode(e: unknown): string | null {
+  return isAppError(e) ? e.code : null;
+}